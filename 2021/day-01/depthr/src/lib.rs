@@ -0,0 +1,325 @@
+//! 2021 day 1: Sonar Sweep
+
+use std::io::BufRead;
+
+use aoc_core::{parse, stream, Answer, ParseError, Solution};
+use aoc_core::alternative::Alternative;
+use aoc_core::generate::{Generated, Rng};
+use aoc_core::stream::StreamError;
+use tracing::{debug, info, trace};
+
+pub struct Day01;
+
+aoc_core::fixture_tests!(Day01);
+
+impl Solution for Day01 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 1;
+
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+        let depths = parse::lines(input, |line| aoc_core::parse_number(line, line))?;
+        debug!("Data file line count: {}", depths.len());
+
+        Ok(depths)
+    }
+
+    fn part_1(depths: &Vec<i32>) -> Answer {
+        star1_improved(depths).into()
+    }
+
+    fn part_2(depths: &Vec<i32>) -> Answer {
+        star2_improved(depths).into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+
+    fn stream(reader: &mut dyn BufRead) -> Option<Result<[Answer; 2], StreamError>> {
+        Some(stream(reader).map(|(increases, window_increases)| [increases.into(), window_increases.into()]))
+    }
+
+    fn alternatives() -> Vec<Alternative<Vec<i32>>> {
+        vec![
+            Alternative::new("original", 1, |depths| star1(depths).into()),
+            Alternative::new("original", 2, |depths| star2(depths).into()),
+        ]
+    }
+}
+
+// Original approach, kept around to compare against star1_improved
+pub fn star1(depths: &[i32]) -> usize {
+    let mut previous = -1;
+    let mut count = 0;
+    let mut increase_count = 0;
+    let mut decrease_count = 0;
+    let mut constant_count = 0;
+    for (i, current) in depths.iter().copied().enumerate() {
+        match i {
+            0 => {
+                previous = current;
+            },
+            _ => {
+                count += 1;
+                if current > previous {
+                    increase_count += 1;
+                }
+                else if current < previous {
+                    decrease_count += 1;
+                }
+                else {
+                    constant_count += 1;
+                }
+
+                // Get ready for next pass
+                previous = current;
+            }
+        }
+    }
+
+    info!(
+        comparisons = count,
+        increases = increase_count,
+        decreases = decrease_count,
+        constant = constant_count,
+        "⭐️ Analysis"
+    );
+
+    increase_count
+}
+
+pub fn star1_improved(depths: &[i32]) -> usize {
+    // Nothing to compare without at least two readings
+    let Some(&first) = depths.first() else {
+        return 0;
+    };
+    let mut previous = first;
+    let mut count = 0;
+    let mut increase_count = 0;
+    let mut decrease_count = 0;
+    let mut constant_count = 0;
+    for current in depths.iter().copied().skip(1) {
+        count += 1;
+        if current > previous {
+            trace!("Depth INCREASED ☝🏻");
+            increase_count += 1;
+        }
+        else if current < previous {
+            trace!("Depth DECREASED 👇🏻");
+            decrease_count += 1;
+        }
+        else {
+            trace!("Depth stayed constant 🔁");
+            constant_count += 1;
+        }
+
+        // Get ready for next pass
+        previous = current;
+        trace!("Updated previous: {}", previous)
+    }
+
+    info!(
+        comparisons = count,
+        increases = increase_count,
+        decreases = decrease_count,
+        constant = constant_count,
+        "⭐️ʹ Analysis"
+    );
+
+    increase_count
+}
+
+// Original approach, kept around to compare against star2_improved
+pub fn star2(depths: &[i32]) -> usize {
+    let mut previous_sum = -1;
+    let mut previous = -1;
+    let mut previous_previous = -1;
+    let mut count = 0;
+    let mut increase_count = 0;
+    let mut decrease_count = 0;
+    let mut constant_count = 0;
+    for (i, current) in depths.iter().copied().enumerate() {
+        match i {
+            0 => {
+                previous_previous = current;
+            },
+            1 => {
+                previous = current;
+            },
+            _ => {
+                let sum = previous_previous + previous + current;
+
+                if previous_sum == -1 {
+                    previous_sum = sum;
+                    previous_previous = previous;
+                    previous = current;
+                    continue;
+                }
+
+                count += 1;
+                if sum > previous_sum {
+                    increase_count += 1;
+                }
+                else if sum < previous_sum {
+                    decrease_count += 1;
+                }
+                else {
+                    constant_count += 1;
+                }
+
+                // Get ready for next pass
+                previous_sum = sum;
+                previous_previous = previous;
+                previous = current;
+            }
+        }
+    }
+
+    info!(
+        comparisons = count,
+        increases = increase_count,
+        decreases = decrease_count,
+        constant = constant_count,
+        "⭐️⭐️ Analysis"
+    );
+
+    increase_count
+}
+
+pub fn star2_improved(depths: &[i32]) -> usize {
+    // Nor without at least two windows, which takes four readings
+    let Some(first) = depths.windows(3).next() else {
+        return 0;
+    };
+    let mut previous_sum: i32 = first.iter().sum();
+    let mut count = 0;
+    let mut increase_count = 0;
+    let mut decrease_count = 0;
+    let mut constant_count = 0;
+    for window in depths.windows(3).skip(1) {
+        let previous_previous = window[0];
+        let previous = window[1];
+        let current = window[2];
+
+        let sum = previous_previous + previous + current;
+        trace!("Calculated sum: {}+{}+{} = {}",previous_previous, previous, current, sum);
+
+        count += 1;
+        if sum > previous_sum {
+            trace!("Depth INCREASED ☝🏻");
+            increase_count += 1;
+        }
+        else if sum < previous_sum {
+            trace!("Depth DECREASED 👇🏻");
+            decrease_count += 1;
+        }
+        else {
+            trace!("Depth stayed constant 🔁");
+            constant_count += 1;
+        }
+
+        // Get ready for next pass
+        previous_sum = sum;
+        trace!("Updated (previous_sum, previous_previous, previous): ({},{},{})", previous_sum, previous_previous, previous)
+    }
+
+    info!(
+        comparisons = count,
+        increases = increase_count,
+        decreases = decrease_count,
+        constant = constant_count,
+        "⭐️⭐️ʹ Analysis"
+    );
+
+    increase_count
+}
+
+/// Both answers in one pass, only ever holding the last three readings. A three-measurement window grows exactly when
+/// the reading entering it is deeper than the one leaving it, three readings before.
+pub fn stream(reader: impl BufRead) -> Result<(u64, u64), StreamError> {
+    let mut last_three: [Option<i32>; 3] = [None; 3];
+    let mut increases = 0;
+    let mut window_increases = 0;
+
+    stream::lines(reader, |line| {
+        let depth: i32 = aoc_core::parse_number(line, line)?;
+
+        if last_three[2].is_some_and(|previous| depth > previous) {
+            increases += 1;
+        }
+        if last_three[0].is_some_and(|leaving| depth > leaving) {
+            window_increases += 1;
+        }
+        last_three = [last_three[1], last_three[2], Some(depth)];
+
+        Ok(())
+    })?;
+
+    info!(increases, window_increases, "⭐️⭐️ Streamed");
+
+    Ok((increases, window_increases))
+}
+
+/// `size` depth readings, wandering deeper on the whole like a real sonar sweep. Both answers can be counted straight
+/// off the readings, since a three-measurement window grows exactly when the reading entering it is deeper than the one
+/// leaving it.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut depths: Vec<i32> = Vec::with_capacity(size.max(4));
+    let mut depth = rng.range(100..=200) as i32;
+    for _ in 0..size.max(4) {
+        depth = (depth + rng.range(0..=50) as i32 - 20).max(0);
+        depths.push(depth);
+    }
+
+    let increases = depths.windows(2).filter(|w| w[1] > w[0]).count();
+    let window_increases = depths.windows(4).filter(|w| w[3] > w[0]).count();
+
+    Generated {
+        input: depths.iter().map(|d| d.to_string()).collect::<Vec<String>>().join("\n"),
+        part_1: Some(increases.into()),
+        part_2: Some(window_increases.into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE: &str = aoc_core::fixture!("sonar-depths-test");
+
+    #[test]
+    fn part_1() {
+        let depths = Day01::parse(SAMPLE).unwrap();
+
+        assert_eq!(7, star1(&depths));
+    }
+
+    #[test]
+    fn part_2() {
+        let depths = Day01::parse(SAMPLE).unwrap();
+
+        assert_eq!(5, star2(&depths));
+    }
+
+    #[test]
+    fn too_few_readings() {
+        for depths in [&[][..], &[5], &[5, 6], &[5, 6, 7]] {
+            assert_eq!(star1(depths), star1_improved(depths), "{:?}", depths);
+            assert_eq!(star2(depths), star2_improved(depths), "{:?}", depths);
+        }
+        assert_eq!(1, star1_improved(&[5, 6]));
+        assert_eq!(0, star2_improved(&[5, 6, 7]));
+    }
+
+    #[test]
+    fn streamed() {
+        assert_eq!((7, 5), stream(SAMPLE.as_bytes()).unwrap());
+
+        match stream("199\n200\n2O8\n".as_bytes()) {
+            Err(StreamError::Parse(e)) => assert_eq!(ParseError::new(3, 1, "2O8", "a number"), e),
+            other => panic!("Expected a parse error, not {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! 2021 day 2: Dive!
+
+use std::io::BufRead;
+
+use aoc_core::{stream, Answer, ParseError, Solution};
+use aoc_core::generate::{Generated, Rng};
+use aoc_core::stream::StreamError;
+use aoc_geometry::{Direction, Point};
+use tracing::{debug, info, trace};
+
+pub struct Day02;
+
+aoc_core::fixture_tests!(Day02);
+
+impl Solution for Day02 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 2;
+
+    type Input = Vec<Nav>;
+
+    fn parse(input: &str) -> Result<Vec<Nav>, ParseError> {
+        input
+            .split('\n')
+            .map(|line| parse_instruction(line).map_err(|e| e.within(input, line)))
+            .collect()
+    }
+
+    fn part_1(instructions: &Vec<Nav>) -> Answer {
+        star1(instructions).into()
+    }
+
+    fn part_2(instructions: &Vec<Nav>) -> Answer {
+        star2(instructions).into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+
+    fn stream(reader: &mut dyn BufRead) -> Option<Result<[Answer; 2], StreamError>> {
+        Some(stream(reader).map(|(star1, star2)| [star1.into(), star2.into()]))
+    }
+}
+
+/// Forward is east, and down is south: the sub's depth is its `y`, growing downwards like a map's.
+#[derive (Debug)]
+pub struct Nav {
+    direction: Direction,
+    magnitude: usize,
+}
+
+pub fn parse_instruction(line: &str) -> Result<Nav, ParseError> {
+    let end_of_line = &line[line.len()..];
+
+    let mut iter = line.split_whitespace();
+    let dir_str = iter.next().unwrap_or(end_of_line);
+    let mag_str = iter.next().unwrap_or(end_of_line);
+    if let Some(extra) = iter.next() {
+        return Err(ParseError::at(line, extra, "the end of the instruction"));
+    }
+
+    let direction = match dir_str {
+        "forward" => Direction::East,
+        "up" => Direction::North,
+        "down" => Direction::South,
+        _ => return Err(ParseError::at(line, dir_str, "forward, up or down")),
+    };
+    let magnitude = aoc_core::parse_number(line, mag_str)?;
+
+    Ok(Nav{direction, magnitude})
+}
+
+pub fn star1(instructions: &[Nav]) -> i64 {
+    debug!("#of instructions: {}", instructions.len());
+
+    let mut position = Point::ORIGIN;
+    for (i, instruction) in instructions.iter().enumerate() {
+        trace!("Processing instruction {:?}", instruction);
+
+        position += instruction.direction.vector() * instruction.magnitude as i64;
+
+        trace!("After processing instruction {} we have (postion, depth)=({}, {})", i, position.x, position.y);
+    }
+
+    let answer = position.x * position.y;
+
+    info!(position = position.x, depth = position.y, answer, "⭐️ Analysis");
+
+    answer
+}
+
+pub fn star2(instructions: &[Nav]) -> i64 {
+    debug!("#of instructions: {}", instructions.len());
+
+    let mut position = Point::ORIGIN;
+    let mut aim = 0;
+    for (i, instruction) in instructions.iter().enumerate() {
+        trace!("Processing instruction {:?}", instruction);
+
+        let magnitude = instruction.magnitude as i64;
+        match instruction.direction {
+            Direction::East => {
+                position.x += magnitude;
+                position.y += aim * magnitude;
+            },
+            // Up and down only turn the sub, by as much as they'd have moved it in part 1
+            direction => {
+                aim += direction.vector().y * magnitude;
+            },
+        }
+
+        trace!("After processing instruction {} we have (postion, depth, aim)=({}, {}, {})", i, position.x, position.y, aim);
+    }
+
+    let answer = position.x * position.y;
+
+    info!(position = position.x, depth = position.y, answer, "⭐️⭐️ Analysis");
+
+    answer
+}
+
+/// Both answers in one pass, steering both ways at once. Positions are kept in 128 bits, as the depth part 2 aims for
+/// grows with the square of the length of the course.
+pub fn stream(reader: impl BufRead) -> Result<(i128, i128), StreamError> {
+    let (mut position, mut depth, mut aimed_depth) = (0i128, 0i128, 0i128);
+
+    stream::lines(reader, |line| {
+        let instruction = parse_instruction(line)?;
+        trace!("Processing instruction {:?}", instruction);
+
+        let magnitude = instruction.magnitude as i128;
+        match instruction.direction {
+            Direction::East => {
+                position += magnitude;
+                aimed_depth += depth * magnitude;
+            },
+            direction => {
+                depth += direction.vector().y as i128 * magnitude;
+            },
+        }
+
+        Ok(())
+    })?;
+
+    info!(position, depth, aimed_depth, "⭐️⭐️ Streamed");
+
+    Ok((position * depth, position * aimed_depth))
+}
+
+/// `size` instructions. The sub never goes up past the surface (or, reading them as aim, never aims above level),
+/// and both answers are plotted as the instructions are made up.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut lines = Vec::with_capacity(size);
+    let (mut position, mut depth, mut aimed_depth): (u128, u128, u128) = (0, 0, 0);
+
+    for _ in 0..size.max(1) {
+        let magnitude = rng.range(1..=9) as u128;
+        match rng.below(3) {
+            0 => {
+                position += magnitude;
+                aimed_depth += depth * magnitude;
+                lines.push(format!("forward {}", magnitude));
+            }
+            1 if depth > 0 => {
+                let magnitude = magnitude.min(depth);
+                depth -= magnitude;
+                lines.push(format!("up {}", magnitude));
+            }
+            _ => {
+                depth += magnitude;
+                lines.push(format!("down {}", magnitude));
+            }
+        }
+    }
+
+    Generated {
+        input: lines.join("\n"),
+        part_1: Some((position * depth).into()),
+        part_2: Some((position * aimed_depth).into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE: &str = aoc_core::fixture!("navigation-test");
+
+    #[test]
+    fn parse() {
+        let instructions = Day02::parse(SAMPLE).unwrap();
+        assert_eq!(6, instructions.len());
+    }
+
+    #[test]
+    fn bad_instructions() {
+        let error = Day02::parse("forward 5\nsideways 3").unwrap_err();
+        assert_eq!(ParseError::new(2, 1, "sideways", "forward, up or down"), error);
+
+        let error = Day02::parse("forward 5\ndown -3").unwrap_err();
+        assert_eq!(ParseError::new(2, 6, "-3", "a number"), error);
+
+        let error = Day02::parse("up").unwrap_err();
+        assert_eq!(ParseError::new(1, 3, "", "a number"), error);
+    }
+
+    #[test]
+    fn streamed() {
+        assert_eq!((150, 900), stream(SAMPLE.as_bytes()).unwrap());
+
+        match stream("forward 5\r\nsideways 3\r\n".as_bytes()) {
+            Err(StreamError::Parse(e)) => assert_eq!(ParseError::new(2, 1, "sideways", "forward, up or down"), e),
+            other => panic!("Expected a parse error, not {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,364 @@
+//! 2021 day 3: Binary Diagnostic
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+use aoc_core::{stream, Answer, ParseError, Solution};
+use aoc_core::generate::{Generated, Rng};
+use aoc_core::stream::StreamError;
+use tracing::{debug, info, trace};
+
+pub struct Day03;
+
+aoc_core::fixture_tests!(Day03);
+
+impl Solution for Day03 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 3;
+
+    /// The report's lines, still as strings of binary digits
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        let lines: Vec<&str> = input.split('\n').collect();
+        let bit_count = lines[0].len();
+
+        if bit_count == 0 {
+            return Err(ParseError::at(input, lines[0], "a binary number"));
+        }
+        if bit_count > MAX_BITS {
+            return Err(ParseError::at(input, lines[0], format!("no more than {} binary digits", MAX_BITS)));
+        }
+
+        for line in lines.iter() {
+            if let Some(i) = line.find(|c| c != '0' && c != '1') {
+                return Err(ParseError::at(input, &line[i..], "a binary digit"));
+            }
+            if line.len() != bit_count {
+                return Err(ParseError::at(input, line, format!("{} binary digits, like the first line", bit_count)));
+            }
+        }
+
+        Ok(lines.iter().map(|l| l.to_string()).collect())
+    }
+
+    fn part_1(lines: &Vec<String>) -> Answer {
+        star1(lines).into()
+    }
+
+    fn part_2(lines: &Vec<String>) -> Answer {
+        star2(lines).into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+
+    fn stream(reader: &mut dyn BufRead) -> Option<Result<[Answer; 2], StreamError>> {
+        Some(stream(reader).map(|(power_consumption, life_support_rating)| [power_consumption.into(), life_support_rating.into()]))
+    }
+}
+
+/// The widest report we can read, as the ratings are worked out as `u32`s
+pub const MAX_BITS: usize = 32;
+
+/// Whether a column's most common bit is a 1, with ties going to 1 as they do for the oxygen generator rating. The
+/// least common bit, for the epsilon rate, is always the other one.
+fn most_common_is_one(ones: u64, total: u64) -> bool {
+    ones * 2 >= total
+}
+
+/// Whether a rating keeps the values with a 1 in the bit it's looking at, given how many of the values left have each:
+/// the most common bit for the oxygen generator rating (`most_common`) and the least common for the CO2 scrubber's,
+/// with ties going to 1 and 0 respectively, as the puzzle says. When every value left has the same bit there's nothing
+/// to choose between, so they're all kept rather than none of them.
+fn keeps_ones(ones: u64, zeros: u64, most_common: bool) -> bool {
+    if ones == 0 || zeros == 0 {
+        return ones > 0;
+    }
+    (ones >= zeros) == most_common
+}
+
+pub fn star1(lines: &[String]) -> usize {
+
+    // let values = lines.iter().map(|l| usize::from_str_radix(l, 2).unwrap() ).collect::<Vec<usize>>();
+
+    debug!("#of lines: {}", lines.len());
+
+    // Every line is as wide as the first, and all binary digits, as parse made sure of
+    let column_count = lines.first().map_or(0, |line| line.len());
+    let mut gamma_rate = 0;
+    let mut epsilon_rate = 0;
+    for column in 0..column_count {
+        let one_count = lines.iter().filter(|line| line.as_bytes()[column] == b'1').count();
+        let zero_count = lines.len() - one_count;
+
+        trace!("    column {} had {} zeros and {} ones", column, zero_count, one_count);
+
+        let gamma_bit = most_common_is_one(one_count as u64, lines.len() as u64) as usize;
+        gamma_rate = gamma_rate << 1 | gamma_bit;
+        epsilon_rate = epsilon_rate << 1 | (1 - gamma_bit);
+
+        trace!("      -> Gamma is now {:b}", gamma_rate);
+    }
+
+    debug!("Gamma rate is {:b} ({} decimal)", gamma_rate, gamma_rate);
+    debug!("Epsilon rate is {:b} ({} decimal)", epsilon_rate, epsilon_rate);
+
+    // This would have been perfect and was super nice when we knew the number of columns a priori. How can I make this dynamic?
+    // Advice from Future Dave: So, remember (2^n - 1) in decimal maps to n set bits in binary. You could calculate the number of bits (n) earlier on and here performed `!gamma_rate & (2^n-1)`
+    // let epsilon_rate = !(gamma_rate) & 0b11111;
+
+    let power_consumption = gamma_rate * epsilon_rate;
+
+    info!(gamma_rate, epsilon_rate, power_consumption, "⭐️ Analysis");
+
+    power_consumption
+}
+
+/// The oxygen generator rating, or nothing if there are no values to rate.
+pub fn find_o2_gen_rating(values: &[u32], max_bits: u32) -> Option<u32> {
+    find_rating(values, max_bits, true)
+}
+
+/// The CO2 scrubber rating, or nothing if there are no values to rate.
+pub fn find_co2_scrubber_rating(values: &[u32], max_bits: u32) -> Option<u32> {
+    find_rating(values, max_bits, false)
+}
+
+fn find_rating(values: &[u32], max_bits: u32, most_common: bool) -> Option<u32> {
+    let mut remaining = values.to_vec();
+
+    // bits are zero indexed
+    for bit in (0..max_bits).rev() {
+        if remaining.len() <= 1 {
+            break;
+        }
+
+        let ones = remaining.iter().filter(|&&v| v & (0b1 << bit) != 0).count() as u64;
+        let zeroes = remaining.len() as u64 - ones;
+        trace!("bit #{} has {} ones and {} zeroes", bit, ones, zeroes);
+
+        let keep = keeps_ones(ones, zeroes, most_common);
+        let pre_len = remaining.len();
+        remaining.retain(|&v| (v & (0b1 << bit) != 0) == keep);
+        trace!("Went from {} to {} keeping values with a {} at bit #{}", pre_len, remaining.len(), keep as u8, bit);
+    }
+
+    // Anything left once the bits run out is a repeat of the same value
+    debug!(remaining = remaining.len(), value = remaining.first(), most_common, "After find_rating loop");
+
+    remaining.first().copied()
+}
+
+pub fn star2(lines: &[String]) -> u64 {
+    let bit_count = lines.first().map_or(0, |line| line.len()) as u32; // test is 5 bit, official is 12
+    // Lines are no wider than MAX_BITS and all binary digits, as parse made sure of
+    let values = lines.iter().map(|l| u32::from_str_radix(l, 2).unwrap_or(0)).collect::<Vec<u32>>();
+
+    let (Some(oxygen_gen_rating), Some(co2_scrubber_rating)) = (find_o2_gen_rating(&values, bit_count), find_co2_scrubber_rating(&values, bit_count)) else {
+        debug!("An empty report has no life support rating");
+        return 0;
+    };
+
+    let life_support_rating = oxygen_gen_rating as u64 * co2_scrubber_rating as u64;
+
+    info!(lines = lines.len(), oxygen_gen_rating, co2_scrubber_rating, life_support_rating, "⭐️⭐️ Analysis");
+
+    life_support_rating
+}
+
+/// Both answers in one pass. Part 1 only needs how many ones each column has. Part 2 needs to narrow the report down
+/// by every bit in turn, so each distinct reading is counted rather than kept: that's at most 2^width of them however
+/// long the report goes on, and no more than 4096 for a real one.
+pub fn stream(reader: impl BufRead) -> Result<(u128, u128), StreamError> {
+    let mut width = None;
+    let mut ones: Vec<u64> = Vec::new();
+    let mut counts: BTreeMap<u64, u64> = BTreeMap::new();
+    let mut total = 0;
+
+    stream::lines(reader, |line| {
+        if let Some(i) = line.find(|c| c != '0' && c != '1') {
+            return Err(ParseError::at(line, &line[i..], "a binary digit"));
+        }
+        let bit_count = *width.get_or_insert(line.len());
+        if line.len() != bit_count {
+            return Err(ParseError::at(line, line, format!("{} binary digits, like the first line", bit_count)));
+        }
+        if bit_count > MAX_BITS {
+            return Err(ParseError::at(line, line, format!("no more than {} binary digits", MAX_BITS)));
+        }
+
+        ones.resize(bit_count, 0);
+        for (column, digit) in line.chars().enumerate() {
+            if digit == '1' {
+                ones[column] += 1;
+            }
+        }
+
+        let value = u64::from_str_radix(line, 2).map_err(|_| ParseError::at(line, line, "a binary number"))?;
+        *counts.entry(value).or_insert(0) += 1;
+        total += 1;
+
+        Ok(())
+    })?;
+
+    if total == 0 {
+        return Err(ParseError::new(1, 1, "", "a binary number").into());
+    }
+
+    let bits = width.unwrap_or(0) as u32;
+    debug!(lines = total, distinct = counts.len(), bits, "Streamed the report");
+
+    let gamma_rate = ones.iter().fold(0u64, |gamma, &count| gamma << 1 | most_common_is_one(count, total) as u64);
+    let epsilon_rate = !gamma_rate & u64::MAX.checked_shr(64 - bits).unwrap_or(0);
+    let power_consumption = gamma_rate as u128 * epsilon_rate as u128;
+
+    let counts: Vec<(u64, u64)> = counts.into_iter().collect();
+    let (Some(oxygen_gen_rating), Some(co2_scrubber_rating)) = (counted_rating(&counts, bits, true), counted_rating(&counts, bits, false)) else {
+        unreachable!("There's at least one value to rate");
+    };
+    let life_support_rating = oxygen_gen_rating as u128 * co2_scrubber_rating as u128;
+
+    info!(gamma_rate, epsilon_rate, power_consumption, oxygen_gen_rating, co2_scrubber_rating, life_support_rating, "⭐️⭐️ Streamed");
+
+    Ok((power_consumption, life_support_rating))
+}
+
+/// [`find_rating`], for distinct values in order along with how many times each turned up
+fn counted_rating(counts: &[(u64, u64)], bits: u32, most_common: bool) -> Option<u64> {
+    let mut running = vec![0];
+    for &(_, count) in counts {
+        running.push(running[running.len() - 1] + count);
+    }
+    let between = |start: usize, end: usize| running[end] - running[start];
+
+    let (mut start, mut end) = (0, counts.len());
+    for bit in (0..bits).rev() {
+        if between(start, end) <= 1 {
+            break;
+        }
+
+        let split = start + counts[start..end].partition_point(|&(v, _)| v & (1 << bit) == 0);
+        let (zeros, ones) = (between(start, split), between(split, end));
+        let keep_ones = keeps_ones(ones, zeros, most_common);
+
+        if keep_ones {
+            start = split;
+        } else {
+            end = split;
+        }
+    }
+
+    if start == end {
+        return None;
+    }
+    Some(counts[start].0)
+}
+
+/// A report of `size` distinct numbers (one more if that would be even, so no column can be tied), with enough bits
+/// to keep them distinct. Values sharing their leading bits sit together once sorted, which makes both ratings a
+/// matter of narrowing down a range.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let count = size.max(3) | 1;
+    let bits = (usize::BITS - count.leading_zeros() + 3).clamp(12, MAX_BITS as u32);
+
+    let mut values = std::collections::BTreeSet::new();
+    while values.len() < count {
+        values.insert(rng.next_u64() as u32 >> (32 - bits));
+    }
+    let values: Vec<u32> = values.into_iter().collect();
+
+    let (oxygen, co2) = (rating(&values, bits, true), rating(&values, bits, false));
+
+    let gamma = (0..bits)
+        .filter(|&bit| most_common_is_one(values.iter().filter(|&&v| v & (1 << bit) != 0).count() as u64, count as u64))
+        .fold(0u64, |g, bit| g | 1 << bit);
+    let epsilon = !gamma & ((1 << bits) - 1);
+
+    let mut lines: Vec<String> = values.iter().map(|v| format!("{:0width$b}", v, width = bits as usize)).collect();
+    rng.shuffle(&mut lines);
+
+    Generated {
+        input: lines.join("\n"),
+        part_1: Some((gamma * epsilon).into()),
+        part_2: Some((oxygen as u64 * co2 as u64).into()),
+    }
+}
+
+/// The oxygen generator (`most_common`) or CO2 scrubber rating of sorted, distinct values, of which there's at least
+/// one.
+fn rating(values: &[u32], bits: u32, most_common: bool) -> u32 {
+    let (mut start, mut end) = (0, values.len());
+
+    for bit in (0..bits).rev() {
+        if end - start == 1 {
+            break;
+        }
+
+        let split = start + values[start..end].partition_point(|v| v & (1 << bit) == 0);
+        let (zeros, ones) = ((split - start) as u64, (end - split) as u64);
+
+        if keeps_ones(ones, zeros, most_common) {
+            start = split;
+        } else {
+            end = split;
+        }
+    }
+
+    values[start]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bad_report() {
+        let error = Day03::parse("00100\n11120").unwrap_err();
+        assert_eq!(ParseError::new(2, 4, "20", "a binary digit"), error);
+
+        let error = Day03::parse("00100\n1110").unwrap_err();
+        assert_eq!(ParseError::new(2, 1, "1110", "5 binary digits, like the first line"), error);
+
+        let error = Day03::parse(&"1".repeat(33)).unwrap_err();
+        assert_eq!(ParseError::new(1, 1, "1".repeat(33), "no more than 32 binary digits"), error);
+
+        assert_eq!(ParseError::new(1, 1, "", "a binary number"), Day03::parse("").unwrap_err());
+    }
+
+    #[test]
+    fn ties() {
+        // Tied columns make gamma 1 and epsilon 0, just as ties keep 1s for oxygen and 0s for CO2
+        let lines = Day03::parse("10\n01").unwrap();
+        assert_eq!(0, star1(&lines));
+        assert_eq!(0b10, star2(&lines));
+        assert_eq!((0, 2), stream("10\n01".as_bytes()).unwrap());
+
+        // When every value left has a 1, the CO2 scrubber keeps them rather than running out
+        let lines = Day03::parse("110\n111\n111").unwrap();
+        assert_eq!(Some(0b111), find_o2_gen_rating(&[0b110, 0b111, 0b111], 3));
+        assert_eq!(Some(0b110), find_co2_scrubber_rating(&[0b110, 0b111, 0b111], 3));
+        assert_eq!(42, star2(&lines));
+        assert_eq!((0, 42), stream("110\n111\n111".as_bytes()).unwrap());
+
+        // A life support rating as wide as the report is can still be multiplied out
+        let widest = Day03::parse(&format!("{}\n0{}", "1".repeat(32), "1".repeat(31))).unwrap();
+        assert_eq!(u32::MAX as u64 * (u32::MAX >> 1) as u64, star2(&widest));
+    }
+
+    #[test]
+    fn streamed() {
+        assert_eq!((198, 230), stream(aoc_core::fixture!("diagnostics-test").as_bytes()).unwrap());
+
+        match stream("00100\n11120".as_bytes()) {
+            Err(StreamError::Parse(e)) => assert_eq!(ParseError::new(2, 4, "20", "a binary digit"), e),
+            other => panic!("Expected a parse error, not {:?}", other),
+        }
+
+        match stream("".as_bytes()) {
+            Err(StreamError::Parse(e)) => assert_eq!(ParseError::new(1, 1, "", "a binary number"), e),
+            other => panic!("Expected a parse error, not {:?}", other),
+        }
+    }
+}
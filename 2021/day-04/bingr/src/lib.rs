@@ -0,0 +1,480 @@
+//! 2021 day 4: Giant Squid
+
+use aoc_core::{parse, Answer, ParseError, Solution};
+use aoc_core::generate::{Generated, Rng};
+use tracing::{debug, info, trace};
+
+pub struct Day04;
+
+aoc_core::fixture_tests!(Day04);
+
+impl Solution for Day04 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 4;
+
+    type Input = Game;
+
+    fn parse(input: &str) -> Result<Game, ParseError> {
+        Game::parse_game_setup(input)
+    }
+
+    fn part_1(game: &Game) -> Answer {
+        star1(game).into()
+    }
+
+    fn part_2(game: &Game) -> Answer {
+        star2(game).into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+}
+
+#[derive (Clone, Debug)]
+pub struct Square {
+    number: u32,
+    marked: bool,
+}
+
+#[derive (Clone, Debug)]
+pub struct Board {
+    squares: Vec<Square>,
+}
+
+#[derive (Clone, Debug)]
+/// Game is the setup for the entire _room_. All players, boards, and the numbers to be called. 
+pub struct Game {
+    numbers: Vec<u32>,
+    boards: Vec<Board>,
+    last_called_number: Option<u32>,
+}
+
+impl Board {
+    /// Calculate the score of the winning board
+    pub fn score(&self, last:u32) -> u32 {
+        self.sum_unmarked_numbers() * last
+    }
+
+    /// Look for a winning condition. Note that only horizontal and vertical rows are considered (no diagonals)
+    pub fn is_winner(&self) -> bool {
+
+        // Check rows
+        for (row, squares) in self.squares.chunks(5).enumerate() {
+            let mut row_all_marked = true;
+            for square in squares.iter() {
+                if !square.marked {
+                    row_all_marked = false;
+                }
+            }
+
+            trace!(
+                "Checking row {}: [{}, {}, {}, {}, {}]... {}",
+                row, squares[0].number, squares[1].number, squares[2].number, squares[3].number, squares[4].number,
+                if row_all_marked { "✅" } else { "❌" }
+            );
+            if row_all_marked {
+                return true;
+            }
+        }
+
+        // Check columns
+        for col in 0..5 {
+            let mut col_all_marked = true;
+            for row_squares in self.squares.chunks(5) {    
+                if !row_squares[col].marked {
+                    col_all_marked = false;
+                }
+            }
+
+            trace!("Checking column {}... {}", col, if col_all_marked { "✅" } else { "❌" });
+            if col_all_marked {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Mark our matching squares, if any. Returns true if one is found.
+    pub fn mark_number_called(&mut self, number:u32) -> bool {
+        let mut marked = false;
+        for i in 0..self.squares.len() {
+            let square = &mut self.squares[i];
+            if square.number == number {
+                square.marked = true;
+                marked = true; // Repeating numbers typically are not allowed but we continue as it wasn't explicitly stated
+            }
+        }
+
+        marked
+    }
+
+    /// Calculate the sum of all unmarked numbers (e.g. the numbers that were not called yet)
+    pub fn sum_unmarked_numbers(&self) -> u32 {
+        let mut sum = 0;
+        for square in self.squares.iter() {
+            if !square.marked {
+                sum += square.number;
+            }
+        }
+        sum
+    }
+}
+
+impl Game {
+    pub fn parse_game_setup(setup: &str) -> Result<Game, ParseError> {
+        let sections = parse::sections(setup);
+        let numbers_csv = sections.first().copied().unwrap_or(setup);
+
+        // First line is a comma-separated list of numbers, in the order to be called
+        let numbers = parse::numbers(numbers_csv, ",").map_err(|e| e.within(setup, numbers_csv))?;
+
+        // Then come the boards, each a table of 5 rows of 5 numbers after a blank line
+        let mut boards:Vec<Board> = Vec::new();
+        for seed in sections.iter().skip(1) {
+            let rows: Vec<Vec<u32>> = parse::table(seed).map_err(|e| e.within(setup, seed))?;
+            if rows.len() != 5 || rows[0].len() != 5 {
+                return Err(ParseError::at(setup, seed, "a board of 5 rows of 5 numbers"));
+            }
+
+            let squares = rows.into_iter().flatten().map(|number| Square{number, marked:false}).collect();
+            boards.push(Board{squares});
+        }
+    
+        Ok(Game{numbers, boards, last_called_number:None})
+    }
+
+    // Plays the game by calling each number in turn, and checking for a winner after each pass. Returns the index of the winning Board, if any.
+    pub fn play(&mut self) -> Option<usize> {
+        for i in 0..self.numbers.len() {
+            let number = self.numbers[i];
+            debug!("📣📣📣📣 CALLING – Step #{} of the game is now calling number {}", i, number);
+
+            self.mark_number_called(number);
+
+            let winner_idx = self.check_for_winner();
+            match winner_idx {
+                Some(idx) => {
+                    debug!("Winner! Step #{} triggered the win when calling {}", i, number);
+                    self.last_called_number = Some(number);
+                    return Some(idx);
+                }
+                None => {
+                    trace!("No winners yet!");
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn last_board_standing(&self) -> Option<usize> {
+        let mut last_idx:Option<usize> = None;
+        let mut incomplete_count = 0;
+
+        trace!(">last_board_standing");
+
+        for (i, board) in self.boards.iter().enumerate() {
+            let winner = board.is_winner();
+            trace!("  checking board {}... {}", i, if winner { "✅" } else { "❌" });
+            if !winner {
+                incomplete_count += 1;
+                last_idx = Some(i);
+            }
+        }
+
+        // Assume there can only be one board last standing (no ties)
+        if incomplete_count == 1 {
+            return last_idx;
+        }
+
+        trace!("< last_board_standing -> None");
+        None
+    }
+
+    // Plays the game until all boards have won. Returns the index of the last winning board, or None if we run out of numbers.
+    pub fn play_until_no_boards_left(&mut self) -> Option<usize> {
+        let mut last_board_idx:Option<usize> = None;
+
+        for i in 0..self.numbers.len() {
+            let number = self.numbers[i];
+            debug!("📣📣📣📣 CALLING – Step #{} of the game is now calling number {}", i, number);
+
+            self.mark_number_called(number);
+
+            match self.last_board_standing() {
+                Some(last_idx) => {
+                    debug!("LAST BOARD STANDING assigned to {} after calling {}", last_idx, number);
+                    last_board_idx = Some(last_idx);
+                },
+                None => {
+                    trace!("   ... no last board stadning...either cause they are all complete or there are multiple incomplete");
+                },
+            }
+
+            let incomplete_boards: usize = self.boards.iter().map(|b| if b.is_winner() {0} else {1} ).sum();
+
+            if incomplete_boards == 0 {
+                debug!("All boards completed!");
+                self.last_called_number = Some(number);
+                break;
+            }
+        }
+
+        debug!("All boards complete! Returning the last board index, {:?}", last_board_idx);
+        last_board_idx
+    }
+
+    pub fn mark_number_called(&mut self, number:u32) {
+        for i in 0..self.boards.len() {
+            let board = &mut self.boards[i];
+            let marked = board.mark_number_called(number);
+            if marked {
+                trace!("Marked! Board {} had a match for {}", i, number);
+            }
+        }
+    }
+
+    /// See if there are any winning boards. Simplifying assumption that there are no ties. Ties are left as an exercise for the reader.
+    pub fn check_for_winner(&self) -> Option<usize> {
+        for (i, board) in self.boards.iter().enumerate() {
+            if board.is_winner() {
+                return Some(i);
+            }
+        }
+
+        None
+    }
+}
+
+pub fn star1(game: &Game) -> u32 {
+    let mut game = game.clone();
+
+    debug!("Game setup has {} numbers to call and {} boards", game.numbers.len(), game.boards.len());
+
+    let winning_idx = game.play().unwrap();
+    let board = &game.boards[winning_idx];
+
+    let score = board.score(game.last_called_number.unwrap());
+    info!(board = winning_idx, unmarked_sum = board.sum_unmarked_numbers(), score, "⭐️ Analysis");
+
+    score
+}
+
+pub fn star2(game: &Game) -> u32 {
+    let mut game = game.clone();
+
+    debug!("Game setup has {} numbers to call and {} boards", game.numbers.len(), game.boards.len());
+
+    let last_idx = game.play_until_no_boards_left().unwrap();
+    let board = &game.boards[last_idx];
+
+    let score = board.score(game.last_called_number.unwrap());
+    info!(last_board = last_idx, unmarked_sum = board.sum_unmarked_numbers(), score, "⭐️⭐️ Analysis");
+
+    score
+}
+
+/// A room of `size` boards, all drawn from the numbers 0 to 99 which are called in a random order. A board wins on the
+/// turn its quickest line is complete, so both answers can be read off without playing. Boards are redrawn until one
+/// wins first and one wins last with no ties, since the puzzle says nothing about those.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    const NUMBERS: usize = 100;
+
+    let mut numbers: Vec<usize> = (0..NUMBERS).collect();
+    rng.shuffle(&mut numbers);
+    let mut called_on = [0; NUMBERS];
+    for (turn, &n) in numbers.iter().enumerate() {
+        called_on[n] = turn;
+    }
+
+    let draw = |rng: &mut Rng| {
+        let mut pool: Vec<usize> = (0..NUMBERS).collect();
+        rng.shuffle(&mut pool);
+        pool.truncate(25);
+        pool
+    };
+    let wins_on = |board: &[usize]| {
+        let rows = (0..5).map(|r| (0..5).map(|c| called_on[board[r * 5 + c]]).max().unwrap());
+        let cols = (0..5).map(|c| (0..5).map(|r| called_on[board[r * 5 + c]]).max().unwrap());
+        rows.chain(cols).min().unwrap()
+    };
+
+    let mut boards: Vec<Vec<usize>> = (0..size.max(2)).map(|_| draw(rng)).collect();
+    let (first, last) = loop {
+        let turns: Vec<usize> = boards.iter().map(|b| wins_on(b)).collect();
+        let first = *turns.iter().min().unwrap();
+        let last = *turns.iter().max().unwrap();
+
+        let firsts: Vec<usize> = (0..boards.len()).filter(|&i| turns[i] == first).collect();
+        let lasts: Vec<usize> = (0..boards.len()).filter(|&i| turns[i] == last).collect();
+        if firsts.len() == 1 && lasts.len() == 1 {
+            break (firsts[0], lasts[0]);
+        }
+
+        // Keep one of each, and try the rest again
+        for &i in firsts.iter().skip(1).chain(lasts.iter().skip(1)) {
+            boards[i] = draw(rng);
+        }
+    };
+
+    let score = |board: &[usize]| {
+        let turn = wins_on(board);
+        board.iter().filter(|&&n| called_on[n] > turn).sum::<usize>() * numbers[turn]
+    };
+
+    let mut input = numbers.iter().map(|n| n.to_string()).collect::<Vec<String>>().join(",");
+    for board in &boards {
+        input.push('\n');
+        for row in board.chunks(5) {
+            input.push('\n');
+            input.push_str(&row.iter().map(|n| format!("{:>2}", n)).collect::<Vec<String>>().join(" "));
+        }
+    }
+
+    Generated {
+        input,
+        part_1: Some(score(&boards[first]).into()),
+        part_2: Some(score(&boards[last]).into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    const INPUT_OFFICIAL_TEST: &str = aoc_core::fixture!("bingo-test");
+
+const INPUT_SHUFFLED_TEST: &str = aoc_core::fixture!("shuffled-boards");
+
+const INPUT_ROW_WINNER_TEST: &str = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+18  8 23 26 20
+22 11 13  6  5
+7  4  9  5 11
+2  0 12  3  7
+21 17 24  04 14 
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6"#;
+
+const INPUT_COL_WINNER_TEST: &str = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+71  4  9  5  7
+18  8 23 26  4
+22 11 13  6  9
+2   0 12  3  5
+21 17 24  4 11 
+
+ 7 13 17 11  0
+ 4  2 23  4 24
+ 9  9 14 16  7
+ 5 10  3 18  5
+17 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6"#;
+
+    #[test]
+    fn play_game_1_official_data() {
+        let mut game = super::Game::parse_game_setup(INPUT_OFFICIAL_TEST).unwrap();
+
+        assert_eq!(game.numbers.len(), 27);
+        assert_eq!(game.boards.len(), 3);
+
+        for (i, board) in game.boards.iter().enumerate() {
+            assert_eq!(board.squares.len(), 25, "Board {} does not have 25 squares", i)
+        }
+
+        assert_eq!(game.boards[0].sum_unmarked_numbers(), 300);
+        assert_eq!(game.boards[1].sum_unmarked_numbers(), 324);
+        assert_eq!(game.boards[2].sum_unmarked_numbers(), 325);
+
+        let winner_idx = game.play();
+
+        assert!(winner_idx.is_some(), "There must be a winner or the players get angry");
+
+        let winning_board = &game.boards[winner_idx.unwrap()];
+        assert_eq!(winning_board.sum_unmarked_numbers(), 188);
+        assert_eq!(winning_board.score(game.last_called_number.unwrap()), 4512);
+    }
+
+    #[test]
+    fn play_game_1_shuffled_data() {
+        let mut game = super::Game::parse_game_setup(INPUT_SHUFFLED_TEST).unwrap();
+        let winner_idx = game.play();
+
+        assert!(winner_idx.is_some(), "There must be a winner or the players get angry");
+
+        let winning_board = &game.boards[winner_idx.unwrap()];
+        assert_eq!(winning_board.sum_unmarked_numbers(), 188);
+        assert_eq!(winning_board.score(game.last_called_number.unwrap()), 4512);
+    }
+
+    #[test]
+    fn play_game_1_row_winner() {
+        let mut game = super::Game::parse_game_setup(INPUT_ROW_WINNER_TEST).unwrap();
+        let winner_idx = game.play().unwrap();
+
+        assert_eq!(winner_idx, 0);
+    }
+
+    #[test]
+    fn play_game_1_col_winner() {
+        let mut game = super::Game::parse_game_setup(INPUT_COL_WINNER_TEST).unwrap();
+        let winner_idx = game.play().unwrap();
+
+        assert_eq!(winner_idx, 0);
+    }
+
+    #[test]
+    fn play_game_2_official_data() {
+        let mut game = super::Game::parse_game_setup(INPUT_OFFICIAL_TEST).unwrap();
+
+        assert_eq!(game.numbers.len(), 27);
+        assert_eq!(game.boards.len(), 3);
+
+        for (i, board) in game.boards.iter().enumerate() {
+            assert_eq!(board.squares.len(), 25, "Board {} does not have 25 squares", i)
+        }
+
+        assert_eq!(game.boards[0].sum_unmarked_numbers(), 300);
+        assert_eq!(game.boards[1].sum_unmarked_numbers(), 324);
+        assert_eq!(game.boards[2].sum_unmarked_numbers(), 325);
+
+        let last_idx = game.play_until_no_boards_left();
+
+        assert_eq!(last_idx.unwrap(), 1);
+
+        let last_board = &game.boards[last_idx.unwrap()];
+
+        assert_eq!(game.last_called_number.unwrap(), 13);
+        assert_eq!(last_board.sum_unmarked_numbers(), 148);
+        assert_eq!(last_board.score(game.last_called_number.unwrap()), 1924);
+    }
+
+    #[test]
+    fn bad_setup() {
+        use aoc_core::ParseError;
+
+        let error = super::Game::parse_game_setup("7,4,9\n\n1 2 3 4 5\n1 2 3 4 5\n1 2 x 4 5\n1 2 3 4 5\n1 2 3 4 5").unwrap_err();
+        assert_eq!(ParseError::new(5, 5, "x", "a number"), error);
+
+        let error = super::Game::parse_game_setup("7,4,9\n\n1 2 3 4 5\n1 2 3 4 5\n1 2 3 4\n1 2 3 4 5\n1 2 3 4 5").unwrap_err();
+        assert_eq!(ParseError::new(5, 1, "1 2 3 4", "a row of 5 numbers"), error);
+
+        let error = super::Game::parse_game_setup("7,4,9\n\n1 2 3 4 5").unwrap_err();
+        assert_eq!(3, error.line);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,424 @@
+//! 2021 day 5: Hydrothermal Venture
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use aoc_core::{parse, Answer, ParseError, Solution};
+use aoc_core::generate::{Generated, Rng};
+use aoc_geometry::{Bounds, Point, Segment};
+use aoc_grid::Grid;
+use aoc_grid::render::{self, Animation, Frame, Rgb};
+use tracing::{debug, info, trace};
+
+pub struct Day05;
+
+aoc_core::fixture_tests!(Day05);
+
+impl Solution for Day05 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 5;
+
+    type Input = OceanFloor;
+
+    fn parse(input: &str) -> Result<OceanFloor, ParseError> {
+        OceanFloor::parse(input)
+    }
+
+    fn part_1(ocean_floor: &OceanFloor) -> Answer {
+        star1(ocean_floor).into()
+    }
+
+    fn part_2(ocean_floor: &OceanFloor) -> Answer {
+        star2(ocean_floor).into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+
+    fn render(ocean_floor: &OceanFloor, to: &Path) -> Option<std::io::Result<()>> {
+        Some(ocean_floor.render().save(to))
+    }
+}
+
+#[derive (Debug, Clone, Copy)]
+pub struct VentLine {
+    line: Segment,
+}
+
+#[derive (Clone, Debug)]
+pub struct OceanFloor {
+    vents: Vec<VentLine>,
+    /// The patch of floor the layout covers, from the origin out to the furthest vent
+    bounds: Bounds,
+    vent_layout: VentLayout,
+}
+
+/// How many vents cross each point of the floor, by its position within the bounds. A real puzzle's floor is a
+/// thousand points across, so it's counted on a grid, but a floor millions of points across only has room for the
+/// points some vent actually crosses.
+#[derive (Clone, Debug)]
+pub enum VentLayout {
+    Dense(Grid<usize>),
+    Sparse(HashMap<(usize, usize), usize>),
+}
+
+impl VentLayout {
+    /// Sixteen real puzzles' worth of floor, and no more, is counted on a grid
+    const MAX_DENSE: usize = 16_000_000;
+
+    fn covering(bounds: &Bounds) -> VentLayout {
+        match bounds.width().checked_mul(bounds.height()) {
+            Some(points) if points <= VentLayout::MAX_DENSE => VentLayout::Dense(Grid::new(bounds.width(), bounds.height(), 0)),
+            _ => VentLayout::Sparse(HashMap::new()),
+        }
+    }
+
+    fn add(&mut self, pos: (usize, usize)) {
+        match self {
+            VentLayout::Dense(grid) => grid[pos] += 1,
+            VentLayout::Sparse(counts) => *counts.entry(pos).or_insert(0) += 1,
+        }
+    }
+
+    pub fn count(&self, pos: (usize, usize)) -> usize {
+        match self {
+            VentLayout::Dense(grid) => grid.get(pos).copied().unwrap_or(0),
+            VentLayout::Sparse(counts) => counts.get(&pos).copied().unwrap_or(0),
+        }
+    }
+
+    /// The layout shrunk `scale` times in each direction to fit a picture, each point showing the most vents crossing
+    /// any point of the square it stands for. Only the points vents cross are looked at, so a sparse layout shrinks
+    /// without ever being filled in.
+    fn shrunk(&self, bounds: &Bounds, scale: usize) -> Grid<usize> {
+        let mut shrunk = Grid::new(bounds.width().div_ceil(scale), bounds.height().div_ceil(scale), 0);
+        let mut add = |(x, y): (usize, usize), count: usize| {
+            let most = &mut shrunk[(x / scale, y / scale)];
+            *most = (*most).max(count);
+        };
+
+        match self {
+            VentLayout::Dense(grid) => grid.iter().for_each(|(pos, &count)| add(pos, count)),
+            VentLayout::Sparse(counts) => counts.iter().for_each(|(&pos, &count)| add(pos, count)),
+        }
+        shrunk
+    }
+
+    /// How many points more than `threshold` vents cross
+    fn count_over(&self, threshold: usize) -> usize {
+        match self {
+            VentLayout::Dense(grid) => grid.cells().filter(|&&c| c > threshold).count(),
+            VentLayout::Sparse(counts) => counts.values().filter(|&&c| c > threshold).count(),
+        }
+    }
+}
+
+impl std::fmt::Display for VentLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+      write!(f, "{}", self.line)
+    }
+}
+
+impl std::fmt::Display for OceanFloor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} vents:", self.vents.len())?;
+        
+        match &self.vent_layout {
+            VentLayout::Dense(grid) => write!(f, "{}", grid.map(|count| format!("{:3} ", count)))?,
+            VentLayout::Sparse(counts) => write!(f, "(too big to draw: {} points crossed)", counts.len())?,
+        }
+        writeln!(f)
+      }
+}
+
+impl VentLine {
+    pub fn parse(serialized_vent_line: &str) -> Result<VentLine, ParseError> {
+        let (start, end) = parse::rule(serialized_vent_line, "two points like 0,9 -> 5,9")?;
+
+        let start = Point::parse(start).map_err(|e| e.within(serialized_vent_line, start))?;
+        let end = Point::parse(end).map_err(|e| e.within(serialized_vent_line, end))?;
+
+        Ok(VentLine{line: Segment::new(start, end)})
+    }
+}
+
+impl OceanFloor {
+    pub fn parse(serialized_vents: &str) -> Result<OceanFloor, ParseError> {
+        let vents = parse::lines(serialized_vents, VentLine::parse)?;
+        debug!("Parsed {} serialized vents", vents.len());
+
+        let bounds = Bounds::at(Point::ORIGIN);
+        let vent_layout = VentLayout::Dense(Grid::new(0, 0, 0));
+        Ok(OceanFloor{vents, bounds, vent_layout})
+    }
+
+    pub fn init_layout(&mut self) {
+        // Setup vent_layout with the appropriate dimensions. Vents may well be off to the left of or above the origin.
+        let ends = self.vents.iter().flat_map(|vent_line| [vent_line.line.start, vent_line.line.end]);
+        self.bounds = ends.fold(Bounds::at(Point::ORIGIN), Bounds::including);
+
+        self.vent_layout = VentLayout::covering(&self.bounds);
+        debug!(
+            "   counting {} x {} floor {}",
+            self.bounds.width(),
+            self.bounds.height(),
+            if matches!(self.vent_layout, VentLayout::Dense(_)) { "on a grid" } else { "sparsely" }
+        );
+        // println!("{}", self);
+    }
+
+    pub fn walk_line(&mut self, vent_line: VentLine, allow_diagonals: bool) {
+        let line = vent_line.line;
+        if !line.is_horizontal() && !line.is_vertical() && !allow_diagonals {
+            trace!("      🙈 skipping diagonal vent {}", vent_line);
+            return;
+        }
+
+        for point in line.points() {
+            trace!("        @{}", point);
+            let pos = self.bounds.index(point).expect("vents are inside the bounds");
+            self.vent_layout.add(pos);
+        }
+        trace!("      ✅ walked vent {}", vent_line);
+    }
+
+    pub fn navigate_horiz_and_vert_vents_to_complete_layout(&mut self) {
+        debug!("@navigate_horiz_and_vert_vents_to_complete_layout");
+        self.init_layout();
+        let vents = self.vents.clone();
+
+        for vent_line in vents.iter() {
+            trace!("   walking the line {}", vent_line);
+            self.walk_line(*vent_line, false);
+            // println!("Layout after walking line #{} {}:\n{}", i, vent_line, self);
+        }
+
+        // println!("Layout after walking horiz&vert vent lines:\n{}", self);
+    }
+
+    pub fn navigate_all_vents_to_complete_layout(&mut self) {
+        debug!("@navigate_all_vents_to_complete_layout");
+        self.init_layout();
+        let vents = self.vents.clone();
+
+        for vent_line in vents.iter() {
+            self.walk_line(*vent_line, true);
+            // println!("Layout after walking line #{} {}:\n{}", i, vent_line, self);
+        }
+
+        // println!("Layout after walking horiz&vert vent lines:\n{}", self);
+    }
+
+    // The floor filling in as every vent is walked, diagonals and all, in about fifty frames. A floor more than
+    // MAX_FRAME_SIDE points across is shrunk to fit rather than drawn a pixel per point.
+    pub fn render(&self) -> Animation {
+        let mut ocean_floor = self.clone();
+        ocean_floor.init_layout();
+        let vents = ocean_floor.vents.clone();
+        let per_frame = (vents.len() / 50).max(1);
+        let scale = ocean_floor.bounds.width().max(ocean_floor.bounds.height()).div_ceil(MAX_FRAME_SIDE).max(1);
+
+        let mut animation = Animation::new();
+        for (i, vent_line) in vents.iter().enumerate() {
+            ocean_floor.walk_line(*vent_line, true);
+            if (i + 1) % per_frame == 0 || i + 1 == vents.len() {
+                let shrunk = ocean_floor.vent_layout.shrunk(&ocean_floor.bounds, scale);
+                animation.push(Frame::of(&shrunk, |&count| overlap_colour(count)));
+            }
+        }
+
+        animation
+    }
+
+    pub fn count_danger_areas(&self) -> usize {
+        debug!("@count_danger_areas");
+        let threshold: usize = 1;
+        self.vent_layout.count_over(threshold)
+    }
+}
+
+/// The most points across a picture of the floor, a real puzzle's worth
+const MAX_FRAME_SIDE: usize = 1000;
+
+/// Open floor is dark and a single vent is blue. Where vents overlap it's dangerous, from yellow for two through to red
+/// for six or more.
+fn overlap_colour(count: usize) -> Rgb {
+    match count {
+        0 => [5, 10, 30],
+        1 => [40, 80, 140],
+        count => render::gradient([255, 210, 60], [230, 30, 30], (count - 2) as f64 / 4.0),
+    }
+}
+
+pub fn star1(ocean_floor: &OceanFloor) -> usize {
+    let mut ocean_floor = ocean_floor.clone();
+
+    debug!("Ocean floor has {} vents", ocean_floor.vents.len());
+
+    ocean_floor.navigate_horiz_and_vert_vents_to_complete_layout();
+
+    let danger_count = ocean_floor.count_danger_areas();
+
+    info!(vents = ocean_floor.vents.len(), danger_count, "⭐️ Analysis");
+
+    danger_count
+}
+
+pub fn star2(ocean_floor: &OceanFloor) -> usize {
+    let mut ocean_floor = ocean_floor.clone();
+
+    debug!("Ocean floor has {} vents", ocean_floor.vents.len());
+
+    ocean_floor.navigate_all_vents_to_complete_layout();
+
+    let danger_count = ocean_floor.count_danger_areas();
+
+    info!(vents = ocean_floor.vents.len(), danger_count, "⭐️⭐️ Analysis");
+
+    danger_count
+}
+
+/// 500 vent lines, like the puzzle's, with coordinates anywhere from 0 to `size`. Lines are kept to a few hundred points
+/// and clustered around a handful of spots so they still cross each other on a huge floor, which also keeps counting the
+/// overlaps point by point cheap.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let size = size.max(2);
+    let spread = (size / 2).min(1000);
+    let spots: Vec<(usize, usize)> = (0..10).map(|_| (rng.range(0..=size), rng.range(0..=size))).collect();
+
+    let mut lines = Vec::new();
+    let mut straight: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut all: HashMap<(usize, usize), usize> = HashMap::new();
+
+    for _ in 0..500 {
+        let &(x, y) = rng.choose(&spots);
+        let start = (
+            rng.range(x.saturating_sub(spread)..=(x + spread).min(size)),
+            rng.range(y.saturating_sub(spread)..=(y + spread).min(size)),
+        );
+
+        // Go whichever way stays on the floor. Each axis has room one way or the other, since length <= size / 2.
+        let length = rng.range(1..=spread) as isize;
+        let (dx, dy) = *rng.choose(&[(1, 0), (0, 1), (1, 1), (1, -1)]);
+        let away = |at: usize, d: isize| if (0..=size as isize).contains(&(at as isize + d * length)) { d } else { -d };
+        let (dx, dy) = (away(start.0, dx), away(start.1, dy));
+
+        for i in 0..=length {
+            let point = ((start.0 as isize + dx * i) as usize, (start.1 as isize + dy * i) as usize);
+            *all.entry(point).or_insert(0) += 1;
+            if dx == 0 || dy == 0 {
+                *straight.entry(point).or_insert(0) += 1;
+            }
+        }
+
+        let end = (start.0 as isize + dx * length, start.1 as isize + dy * length);
+        lines.push(format!("{},{} -> {},{}", start.0, start.1, end.0, end.1));
+    }
+
+    Generated {
+        input: lines.join("\n"),
+        part_1: Some(straight.values().filter(|&&n| n > 1).count().into()),
+        part_2: Some(all.values().filter(|&&n| n > 1).count().into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    const INPUT_SAMPLE: &str = aoc_core::fixture!("example");
+
+    const INPUT_PARTIAL_OFFICIAL: &str = r#"456,846 -> 221,846
+980,926 -> 73,19
+682,930 -> 562,930
+766,592 -> 274,100
+247,685 -> 247,21"#;
+
+   #[test]
+    fn first_star_official_sample() {
+        let mut ocean_floor = super::OceanFloor::parse(INPUT_SAMPLE).unwrap();
+
+        assert_eq!(10, ocean_floor.vents.len());
+
+        ocean_floor.navigate_horiz_and_vert_vents_to_complete_layout();
+
+        assert_eq!(5, ocean_floor.count_danger_areas());
+    }
+
+    #[test]
+    fn first_star_first_part_of_official() {
+        let mut ocean_floor = super::OceanFloor::parse(INPUT_PARTIAL_OFFICIAL).unwrap();
+
+        assert_eq!(5, ocean_floor.vents.len());
+
+        ocean_floor.navigate_horiz_and_vert_vents_to_complete_layout();
+
+        assert_eq!(0, ocean_floor.count_danger_areas());
+    }
+
+    #[test]
+    fn second_star_given_sample() {
+        let mut ocean_floor = super::OceanFloor::parse(INPUT_SAMPLE).unwrap();
+
+        assert_eq!(10, ocean_floor.vents.len());
+
+        ocean_floor.navigate_all_vents_to_complete_layout();
+
+        assert_eq!(12, ocean_floor.count_danger_areas());
+    }
+
+    #[test]
+    fn negative_coordinates() {
+        let mut ocean_floor = super::OceanFloor::parse("-3,-1 -> 2,-1\n0,-4 -> 0,2\n-2,2 -> 2,-2").unwrap();
+
+        ocean_floor.navigate_horiz_and_vert_vents_to_complete_layout();
+        assert_eq!(1, ocean_floor.count_danger_areas());
+
+        ocean_floor.navigate_all_vents_to_complete_layout();
+        assert_eq!(3, ocean_floor.count_danger_areas());
+    }
+
+    #[test]
+    fn huge_floor() {
+        let mut ocean_floor = super::OceanFloor::parse("0,0 -> 3,0\n2,0 -> 2,2\n4000000,5000000 -> 4000002,5000000\n4000001,5000000 -> 4000001,4999999").unwrap();
+
+        ocean_floor.navigate_all_vents_to_complete_layout();
+        assert!(matches!(ocean_floor.vent_layout, super::VentLayout::Sparse(_)));
+        assert_eq!(2, ocean_floor.count_danger_areas());
+
+        // Drawn shrunk, with the overlaps near the origin still showing
+        let animation = ocean_floor.render();
+        let last = animation.frames().last().unwrap();
+        assert_eq!((800, 1000), (last.width(), last.height()));
+        assert_eq!(Some(super::overlap_colour(2)), last.get((0, 0)));
+        assert_eq!(Some(super::overlap_colour(0)), last.get((1, 0)));
+        assert_eq!(Some(super::overlap_colour(2)), last.get((799, 999)));
+    }
+
+    #[test]
+    fn render() {
+        let ocean_floor = super::OceanFloor::parse(INPUT_SAMPLE).unwrap();
+        let animation = ocean_floor.render();
+
+        // A frame per vent, since there are so few
+        assert_eq!(10, animation.frames().len());
+
+        let last = animation.frames().last().unwrap();
+        assert_eq!(Some(super::overlap_colour(1)), last.get((0, 0)));
+        assert_eq!(Some(super::overlap_colour(0)), last.get((1, 0)));
+        assert_eq!(Some(super::overlap_colour(3)), last.get((4, 4)));
+    }
+
+    #[test]
+    fn bad_vents() {
+        use aoc_core::ParseError;
+
+        let error = super::OceanFloor::parse("0,9 -> 5,9\n8,0 -> 0,x").unwrap_err();
+        assert_eq!(ParseError::new(2, 10, "x", "a number"), error);
+
+        let error = super::OceanFloor::parse("0,9 -> 5,9\n8,0 -> 0,8,1").unwrap_err();
+        assert_eq!(ParseError::new(2, 8, "0,8,1", "a point like 3,4"), error);
+
+        let error = super::OceanFloor::parse("0,9 => 5,9").unwrap_err();
+        assert_eq!(ParseError::new(1, 1, "0,9 => 5,9", "two points like 0,9 -> 5,9"), error);
+    }
+}
\ No newline at end of file
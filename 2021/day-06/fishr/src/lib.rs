@@ -0,0 +1,371 @@
+//! 2021 day 6: Lanternfish
+
+use std::io::Write;
+
+use aoc_core::{parse, Answer, ParseError, Solution};
+use aoc_core::animate::{self, paint, Colour, Screen};
+use aoc_core::count::{self, BigInt, Count, Counting, Overflow};
+use aoc_core::generate::{Generated, Rng};
+use tracing::{info, trace};
+
+pub struct Day06;
+
+aoc_core::fixture_tests!(Day06);
+
+impl Solution for Day06 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 6;
+
+    type Input = School;
+
+    fn parse(input: &str) -> Result<School, ParseError> {
+        School::parse(input)
+    }
+
+    fn part_1(school: &School) -> Answer {
+        count::exact_answer(|| star1(school), || star1(&school.counted::<BigInt>()))
+    }
+
+    fn part_2(school: &School) -> Answer {
+        count::exact_answer(|| star2(school), || star2(&school.counted::<BigInt>()))
+    }
+
+    /// Part 1 counts fish for 80 days and part 2 for 256, unless told otherwise
+    fn count(school: &School, part: u8, counting: &Counting) -> Option<Result<Answer, Overflow>> {
+        let days = counting.steps_or(if part == 1 { 80 } else { 256 });
+        Some(counting.answer(|| fish_after(school, days), || fish_after(&school.counted::<BigInt>(), days)))
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+
+    fn animate(school: &School, screen: &mut Screen) -> Option<std::io::Result<()>> {
+        Some(school.animate(screen))
+    }
+}
+
+// #[derive(Debug)]
+// struct Lanternfish {
+//     count: usize,  // how many fish w/ this timer are there?
+// }
+
+/// Counted in 64 bits unless told otherwise. Past a few hundred days there are more fish than that, and a
+/// `School<BigInt>` keeps counting exactly for as long as there's memory.
+#[derive(Clone, Debug)]
+pub struct School<C: Count = u64> {
+    day: usize,                 // the day of the sim we are on
+    fish: [C; 9],    // we collect like fish together so we only need 9 (0-8). array position represents the time left till spawn, and value is the count
+}
+
+// This only worked when each fish was kept separate. Exponential growth prevented us from doing that
+// impl std::fmt::Display for School {
+//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+//         if self.day == 0 {
+//             write!(f, "Initial state: ");
+//         } else if self.day == 1 {
+//             write!(f, "After  1 day:  ");
+//         } else {
+//             write!(f, "After {:2} days: ", self.day);
+//         }
+
+//         let mut fishies = self.fish.iter().peekable();
+//         while let Some(fish) = fishies.next() {
+//             write!(f, "{}", fish.timer);
+//             if fishies.peek().is_some() {
+//                 write!(f, ",");
+//             }
+//         }
+//         write!(f, "")
+//     }
+// }
+
+// impl Lanternfish {
+//     // Returns true if a new Lanternfish was spawned
+//     fn age_by_a_day(&mut self) -> bool {
+//         if self.timer == 0 {
+//             self.timer = 6;
+//             return true;
+//         }
+
+//         self.timer -= 1;
+//         false
+//     }
+// }
+
+impl School {
+    pub fn parse(input: &str) -> Result<School, ParseError> {
+        let mut fish:[u64; 9] = [0,0,0,0,0,0,0,0,0];
+        // for i in 0..9 {
+        //     fish[i] = 0;
+        // }
+
+        trace!("Parsing <{}>", input);
+
+        let timers: Vec<usize> = parse::separated(input, ",", |i| match aoc_core::parse_number(i, i)? {
+            timer if timer < fish.len() => Ok(timer), // remaining time for this fish
+            _ => Err(ParseError::at(i, i, "a timer from 0 to 8")),
+        })?;
+        for timer in timers {
+           fish[timer] += 1;
+        }
+
+        Ok(School { day: 0, fish })
+    }
+
+    /// The same school, counted some other way
+    pub fn counted<C: Count>(&self) -> School<C> {
+        School { day: self.day, fish: self.fish.map(C::from_u64) }
+    }
+}
+
+impl<C: Count> School<C> {
+    pub fn number_of_fish(&self) -> Result<C, Overflow> {
+        count::sum(&self.fish)
+    }
+
+    /// Stops with an error as soon as any count overflows, leaving the school part way through that day.
+    pub fn age_by_n_days(&mut self, n: usize) -> Result<(), Overflow> {
+        // println!("Aging school of fishies by {} days\n\n{}", n, self);
+
+        for _ in 1..n+1 {
+            // println!("Day {}: {}", i, self.number_of_fish());
+
+            // Those fish @ 0 are about to spawn. Everyone else moves down a day, and the spawners go round to 8, which
+            // is where spawned fish start
+            self.fish.rotate_left(1);
+            let spawners = self.fish[8].clone();
+
+            // Spawning fish reset to 6
+            self.fish[6].try_add_assign(&spawners)?;
+
+            self.day += 1;
+        }
+
+        Ok(())
+    }
+}
+
+// How many fish have each timer, as a bar chart
+impl std::fmt::Display for School {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Nine 64 bit counts always add up in 128 bits
+        let buckets: Vec<(String, u128)> = self.fish.iter().enumerate().map(|(timer, &count)| (timer.to_string(), count as u128)).collect();
+        writeln!(f, "After {:3} days: {} fish", self.day, buckets.iter().map(|(_, count)| count).sum::<u128>())?;
+
+        write!(f, "{}", animate::bars(&buckets, 40))
+    }
+}
+
+impl School {
+    // Every day up to part 2's, with the fish about to spawn picked out
+    pub fn animate(&self, screen: &mut Screen<impl Write>) -> std::io::Result<()> {
+        let mut school = self.clone();
+
+        loop {
+            // The line after the heading is timer 0
+            let chart: Vec<String> = school
+                .to_string()
+                .lines()
+                .enumerate()
+                .map(|(i, line)| if i == 1 { paint(line, Colour::Yellow) } else { line.to_string() })
+                .collect();
+            screen.show(&chart.join("\n"))?;
+
+            if school.day >= 256 {
+                return Ok(());
+            }
+            if school.age_by_n_days(1).is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// How many fish there are once the school has aged by `days` days
+pub fn fish_after<C: Count>(school: &School<C>, days: usize) -> Result<C, Overflow> {
+    let mut school = school.clone();
+
+//    println!("Starting with {} fish:\n{}", school.fish.len(), school);
+    school.age_by_n_days(days)?;
+
+    school.number_of_fish()
+}
+
+pub fn star1<C: Count>(school: &School<C>) -> Result<C, Overflow> {
+    let fish = fish_after(school, 80)?;
+    info!(%fish, "⭐️ Analysis");
+ 
+    Ok(fish)
+}
+
+pub fn star2<C: Count>(school: &School<C>) -> Result<C, Overflow> {
+    let fish = fish_after(school, 256)?;
+    info!(%fish, "⭐️⭐️ Analysis");
+ 
+    Ok(fish)
+}
+
+/// A school of `size` fish with timers from 1 to 5, as in the puzzle. Every fish with the same timer has the same
+/// number of descendants, so the answers are just how many fish one of each grows into, scaled up. That's worked out
+/// in 128 bits, which is plenty for any school that fits in memory.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let timers: Vec<usize> = (0..size.max(1)).map(|_| rng.range(1..=5)).collect();
+
+    let mut counts = [0u128; 9];
+    for &timer in &timers {
+        counts[timer] += 1;
+    }
+
+    let school_after = |days: usize| -> u128 {
+        let descendants = |timer: usize| {
+            let mut fish = [0u128; 9];
+            fish[timer] = 1;
+            for _ in 0..days {
+                fish.rotate_left(1);
+                fish[6] += fish[8];
+            }
+            fish.iter().sum::<u128>()
+        };
+
+        (0..9).map(|timer| counts[timer] * descendants(timer)).sum()
+    };
+
+    Generated {
+        input: timers.iter().map(|t| t.to_string()).collect::<Vec<String>>().join(","),
+        part_1: Some(school_after(80).into()),
+        part_2: Some(school_after(256).into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    const INPUT_SAMPLE: &str = aoc_core::fixture!("example");
+    #[allow(dead_code)]
+    const SAMPLE_RESULT_AFTER_18_DAYS: &str = r#"After 18 days: 6,0,6,4,5,6,0,1,1,2,6,0,1,1,1,2,2,3,3,4,6,7,8,8,8,8"#;
+
+    #[test]
+    fn baby_steps() {
+        let mut school = super::School::parse(INPUT_SAMPLE).unwrap();
+
+        // Initial state: 3,4,3,1,2
+        assert_eq!(0, school.day);
+        assert_eq!(5, school.number_of_fish().unwrap());
+
+        // After  1 day:  2,3,2,0,1
+        school.age_by_n_days(1).unwrap();
+        assert_eq!(1, school.day);
+        assert_eq!(5, school.number_of_fish().unwrap());
+
+        // After  2 days: 1,2,1,6,0,8
+        school.age_by_n_days(1).unwrap();
+        assert_eq!(6, school.number_of_fish().unwrap());
+
+        // After  3 days: 0,1,0,5,6,7,8
+        school.age_by_n_days(1).unwrap();
+        assert_eq!(7, school.number_of_fish().unwrap());
+
+        // After  4 days: 6,0,6,4,5,6,7,8,8
+        school.age_by_n_days(1).unwrap();
+        assert_eq!(9, school.number_of_fish().unwrap());
+
+        // After  5 days: 5,6,5,3,4,5,6,7,7,8
+        school.age_by_n_days(1).unwrap();
+        assert_eq!(10, school.number_of_fish().unwrap());
+
+        // After  6 days: 4,5,4,2,3,4,5,6,6,7
+        school.age_by_n_days(1).unwrap();
+        assert_eq!(10, school.number_of_fish().unwrap());
+
+        // After  7 days: 3,4,3,1,2,3,4,5,5,6
+        school.age_by_n_days(1).unwrap();
+        assert_eq!(10, school.number_of_fish().unwrap());
+
+        // After  8 days: 2,3,2,0,1,2,3,4,4,5
+        school.age_by_n_days(1).unwrap();
+        assert_eq!(10, school.number_of_fish().unwrap());
+
+        // After  9 days: 1,2,1,6,0,1,2,3,3,4,8
+        school.age_by_n_days(1).unwrap();
+        assert_eq!(11, school.number_of_fish().unwrap());
+
+        // After 10 days: 0,1,0,5,6,0,1,2,2,3,7,8
+        school.age_by_n_days(1).unwrap();
+        assert_eq!(12, school.number_of_fish().unwrap());
+    }
+
+    #[test]
+    fn part_1_after_18_days() {
+        let mut school = super::School::parse(INPUT_SAMPLE).unwrap();
+
+//        println!("Loaded school of lanternfish:\n\n{}", school);
+        assert_eq!(0, school.day);
+        assert_eq!(5, school.number_of_fish().unwrap());
+
+        school.age_by_n_days(18).unwrap();
+        assert_eq!(18, school.day);
+        assert_eq!(26, school.number_of_fish().unwrap());
+//        assert_eq!(SAMPLE_RESULT_AFTER_18_DAYS, format!("{}", school));
+    }
+
+    #[test]
+    fn part_1_after_80_days() {
+        let mut school = super::School::parse(INPUT_SAMPLE).unwrap();
+        school.age_by_n_days(80).unwrap();
+        assert_eq!(5934, school.number_of_fish().unwrap());
+    }
+
+    #[test]
+    fn part_2() {
+        let mut school = super::School::parse(INPUT_SAMPLE).unwrap();
+        school.age_by_n_days(256).unwrap();
+        assert_eq!(26984457539, school.number_of_fish().unwrap());
+    }
+
+    #[test]
+    fn a_thousand_days() {
+        let school = super::School::parse(INPUT_SAMPLE).unwrap();
+
+        let mut checked = school.clone();
+        assert_eq!(Err(super::Overflow), checked.age_by_n_days(1000));
+
+        let mut exact = school.counted::<super::BigInt>();
+        exact.age_by_n_days(1000).unwrap();
+        assert_eq!("379589061144698259131825683795505058481", exact.number_of_fish().unwrap().to_string());
+    }
+
+    #[test]
+    fn counting_past_the_puzzle() {
+        use aoc_core::count::Counting;
+        use aoc_core::{Answer, Solution};
+
+        let school = super::School::parse(INPUT_SAMPLE).unwrap();
+        let count = |steps, big| super::Day06::count(&school, 2, &Counting { steps, big }).unwrap();
+
+        assert_eq!(Ok(Answer::Integer(26984457539)), count(None, false));
+        assert_eq!(Ok(Answer::Integer(26)), count(Some(18), false));
+        assert_eq!(Err(super::Overflow), count(Some(1000), false));
+        assert_eq!("379589061144698259131825683795505058481", count(Some(1000), true).unwrap().to_string());
+    }
+
+    #[test]
+    fn display() {
+        let mut school = super::School::parse(INPUT_SAMPLE).unwrap();
+        school.age_by_n_days(18).unwrap();
+
+        let chart = school.to_string();
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(10, lines.len());
+        assert_eq!("After  18 days: 26 fish", lines[0]);
+        assert_eq!(format!("1 {} 5", "█".repeat(40)), lines[2]);
+        assert_eq!(format!("8 {} 4", "█".repeat(32)), lines[9]);
+    }
+
+    #[test]
+    fn bad_timers() {
+        use aoc_core::ParseError;
+
+        assert_eq!(Err(ParseError::new(1, 5, "9", "a timer from 0 to 8")), super::School::parse("3,4,9,1").map(|s| s.fish));
+        assert_eq!(Err(ParseError::new(1, 3, "", "a number")), super::School::parse("3,,1").map(|s| s.fish));
+    }
+}
@@ -0,0 +1,245 @@
+//! 2021 day 7: The Treachery of Whales
+
+use aoc_core::{parse, Answer, ParseError, Solution};
+use aoc_core::alternative::Alternative;
+use aoc_core::generate::{Generated, Rng};
+use tracing::{info, trace};
+
+pub struct Day07;
+
+aoc_core::fixture_tests!(Day07);
+
+impl Solution for Day07 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 7;
+
+    type Input = Swarm;
+
+    fn parse(input: &str) -> Result<Swarm, ParseError> {
+        Swarm::parse(input)
+    }
+
+    fn part_1(swarm: &Swarm) -> Answer {
+        star1(swarm).into()
+    }
+
+    fn part_2(swarm: &Swarm) -> Answer {
+        star2(swarm).into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+
+    fn alternatives() -> Vec<Alternative<Swarm>> {
+        vec![
+            Alternative::new("median", 1, |swarm| star1_median(swarm).into()),
+            Alternative::new("near the mean", 2, |swarm| star2_near_mean(swarm).into()),
+        ]
+    }
+}
+
+#[derive(Debug)]
+pub struct Crab {
+    horizontal_pos: usize,
+}
+
+#[derive(Debug)]
+pub struct Swarm {
+    crabs: Vec<Crab>,
+}
+
+impl Crab {
+    // Calculate how far this crab is from horizontal position p
+    pub fn distance_from(&self, p: usize) -> usize {
+        p.abs_diff(self.horizontal_pos)
+    }
+
+    pub fn fuel_cost_to_move_to(&self, p:usize) -> usize {
+        let distance = self.distance_from(p);
+        let mut sum = 0;
+        for i in 0..distance + 1 {
+            sum += i;
+        }
+
+        sum
+    }
+}
+
+impl Swarm {
+    pub fn parse(input: &str) -> Result<Swarm, ParseError> {
+        trace!("Parsing <{}>", input);
+        let crabs = parse::numbers(input, ",")?.into_iter().map(|horizontal_pos| Crab { horizontal_pos }).collect();
+
+        Ok(Swarm { crabs })
+    }
+
+    pub fn max_hpos(&self) -> usize {
+        let mut max = 0;
+        for crab in self.crabs.iter() {
+            if crab.horizontal_pos > max {
+                max = crab.horizontal_pos;
+            }
+        }
+
+        max
+    }
+
+    pub fn total_fuel_cost_to_position_using_constant_fuel(&self, hpos: usize) -> usize {
+        let mut total = 0;
+        for crab in self.crabs.iter() {
+            total += crab.distance_from(hpos);
+        }
+        total
+    }
+
+    pub fn ideal_horizontal_pos_using_constant_fuel(&self) -> usize {
+        let mut min_fuel_cost = usize::MAX;
+        let mut ideal_position = usize::MAX;
+
+        for hcol in 0..self.max_hpos() {
+            let fuel = self.total_fuel_cost_to_position_using_constant_fuel(hcol);
+            if fuel < min_fuel_cost {
+                min_fuel_cost = fuel;
+                ideal_position = hcol;
+            }
+
+            trace!("   Fuel cost to {:2}: {:5}", hcol, fuel);
+        }
+
+        ideal_position
+    }
+
+    pub fn total_fuel_cost_to_position_using_exponential_fuel(&self, hpos: usize) -> usize {
+        let mut total = 0;
+        for crab in self.crabs.iter() {
+            total += crab.fuel_cost_to_move_to(hpos);
+        }
+        total
+    }
+
+    pub fn ideal_horizontal_pos_using_exponential_fuel(&self) -> (usize, usize) {
+        let mut min_fuel_cost = usize::MAX;
+        let mut ideal_position = usize::MAX;
+
+        for hcol in 0..self.max_hpos() {
+            let fuel = self.total_fuel_cost_to_position_using_exponential_fuel(hcol);
+            if fuel < min_fuel_cost {
+                min_fuel_cost = fuel;
+                ideal_position = hcol;
+            }
+
+            trace!("   Fuel cost to {:2}: {:5}", hcol, fuel);
+        }
+
+        (ideal_position, min_fuel_cost)
+    }
+}
+
+pub fn star1(swarm: &Swarm) -> usize {
+    let ideal = swarm.ideal_horizontal_pos_using_constant_fuel();
+
+    let fuel = swarm.total_fuel_cost_to_position_using_constant_fuel(ideal);
+    info!(crabs = swarm.crabs.len(), max_position = swarm.max_hpos(), ideal, fuel, "⭐️ Analysis");
+
+    fuel
+}
+
+pub fn star2(swarm: &Swarm) -> usize {
+    let (ideal, fuel) = swarm.ideal_horizontal_pos_using_exponential_fuel();
+
+    info!(crabs = swarm.crabs.len(), max_position = swarm.max_hpos(), ideal, fuel, "⭐️⭐️ Analysis");
+
+    fuel
+}
+
+// Not yet trusted to replace the brute force search: with fuel by the step, the best position is the median
+pub fn star1_median(swarm: &Swarm) -> usize {
+    let mut positions: Vec<usize> = swarm.crabs.iter().map(|c| c.horizontal_pos).collect();
+    positions.sort();
+
+    let median = positions.get(positions.len() / 2).copied().unwrap_or(0);
+    swarm.total_fuel_cost_to_position_using_constant_fuel(median)
+}
+
+// Not yet trusted to replace the brute force search: with fuel growing each step, the best position is always within
+// a step of the mean
+pub fn star2_near_mean(swarm: &Swarm) -> usize {
+    let mean = swarm.crabs.iter().map(|c| c.horizontal_pos).sum::<usize>() / swarm.crabs.len().max(1);
+
+    (mean.saturating_sub(1)..=mean + 1)
+        .map(|p| swarm.total_fuel_cost_to_position_using_exponential_fuel(p))
+        .min()
+        .unwrap()
+}
+
+/// `size` crabs spread between 0 and `size`. With fuel by the step the best position is the median; with fuel growing
+/// each step it's always within a step of the mean, so only a few positions need costing either way.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let mut crabs: Vec<usize> = (0..size.max(3)).map(|_| rng.range(0..=size.max(3))).collect();
+    let input = crabs.iter().map(|c| c.to_string()).collect::<Vec<String>>().join(",");
+
+    crabs.sort();
+    let median = crabs[crabs.len() / 2];
+    let constant: u128 = crabs.iter().map(|&c| c.abs_diff(median) as u128).sum();
+
+    let mean = crabs.iter().sum::<usize>() / crabs.len();
+    let growing = (mean.saturating_sub(1)..=mean + 2)
+        .map(|p| crabs.iter().map(|&c| c.abs_diff(p) as u128).map(|d| d * (d + 1) / 2).sum::<u128>())
+        .min()
+        .unwrap();
+
+    Generated {
+        input,
+        part_1: Some(constant.into()),
+        part_2: Some(growing.into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    const INPUT_SAMPLE: &str = aoc_core::fixture!("example");
+
+    #[test]
+    fn part_1() {
+        let swarm = super::Swarm::parse(INPUT_SAMPLE).unwrap();
+
+        assert_eq!(10, swarm.crabs.len());
+        assert_eq!(16, swarm.max_hpos());
+
+        assert_eq!(5, swarm.crabs[2].distance_from(7));
+        assert_eq!(2, swarm.crabs[2].distance_from(0));
+
+        assert_eq!(2, swarm.ideal_horizontal_pos_using_constant_fuel());
+    }
+
+    #[test]
+    fn part_2() {
+        let crab = super::Crab{horizontal_pos: 5};
+        assert_eq!(0, crab.fuel_cost_to_move_to(5));
+
+        assert_eq!(1, crab.fuel_cost_to_move_to(6));
+        assert_eq!(1, crab.fuel_cost_to_move_to(4));
+
+        assert_eq!(3, crab.fuel_cost_to_move_to(7));
+        assert_eq!(3, crab.fuel_cost_to_move_to(3));
+
+        assert_eq!(6, crab.fuel_cost_to_move_to(8));
+        assert_eq!(6, crab.fuel_cost_to_move_to(2));
+
+        assert_eq!(10, crab.fuel_cost_to_move_to(9));
+        assert_eq!(10, crab.fuel_cost_to_move_to(1));
+
+        assert_eq!(15, crab.fuel_cost_to_move_to(10));
+        assert_eq!(15, crab.fuel_cost_to_move_to(0));
+
+        let swarm = super::Swarm::parse(INPUT_SAMPLE).unwrap();
+
+        assert_eq!(10, swarm.crabs.len());
+        assert_eq!(16, swarm.max_hpos());
+
+        let (ideal, cost) = swarm.ideal_horizontal_pos_using_exponential_fuel();
+        assert_eq!(5, ideal);
+        assert_eq!(168, cost);
+    }
+}
@@ -0,0 +1,538 @@
+//! 2021 day 9: Smoke Basin
+
+use std::path::Path;
+
+use aoc_core::{Answer, ParseError, Solution};
+use aoc_core::alternative::Alternative;
+use aoc_core::generate::{Generated, Rng};
+use aoc_geometry::Point;
+use aoc_grid::Grid;
+use aoc_grid::render::{self, Animation, Frame, Rgb, BLACK};
+use tracing::{debug, info, trace};
+
+pub struct Day09;
+
+aoc_core::fixture_tests!(Day09);
+
+impl Solution for Day09 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 9;
+
+    type Input = Heightmap;
+
+    fn parse(input: &str) -> Result<Heightmap, ParseError> {
+        Heightmap::parse(input)
+    }
+
+    fn part_1(heightmap: &Heightmap) -> Answer {
+        star1(heightmap).into()
+    }
+
+    fn part_2(heightmap: &Heightmap) -> Answer {
+        star2(heightmap).into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+
+    fn render(heightmap: &Heightmap, to: &Path) -> Option<std::io::Result<()>> {
+        Some(heightmap.render().save(to))
+    }
+
+    fn alternatives() -> Vec<Alternative<Heightmap>> {
+        vec![Alternative::new("one flood", 2, |heightmap| star2_one_flood(heightmap).into())]
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Cell {
+    height: usize,
+    at: Point,
+}
+
+#[derive(Debug)]
+pub struct Heightmap {
+    cells: Grid<Cell>,
+}
+
+impl std::fmt::Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+      write!(f, "{}={}", self.at, self.height)
+    }
+}
+
+impl Cell {
+    pub fn risk_level(&self) -> usize {
+        self.height + 1
+    }
+
+    // mark this cell as non-navigatible for future walkers
+    // This method makes assumptions and should be removed since the walking table could be modelled with optionals
+    pub fn mark_as_non_walkable(&mut self) {
+        self.height = 9;
+    }
+}
+
+impl Heightmap {
+    pub fn parse(input: &str) -> Result<Heightmap, ParseError> {
+        trace!("Parsing <{}>", input);
+        let heights: Grid<usize> = Grid::parse_digits(input)?;
+        let cells = Grid::from_fn(heights.width(), heights.height(), |pos| Cell { height: heights[pos], at: Point::from(pos) });
+
+        Ok(Heightmap { cells })
+    }
+
+    #[cfg(test)] // only used to sanity check our coordinate model
+    fn adjacent_sum(&self, at: Point) -> usize {
+        self.neighbours(at).map(|p| self.cells[p].height).sum()
+    }
+
+    // The cells up, down, left and right of this one that are actually on the map
+    fn neighbours(&self, at: Point) -> impl Iterator<Item = Point> + '_ {
+        at.neighbours_4().filter(|&p| self.cells.at(p).is_some())
+    }
+
+    // A cell is the low point if its height is *lower* than any of its adjacent locations. Note the *lower* and not *less than or equal*. 
+    pub fn is_low_point(&self, at: Point) -> bool {
+        let me = self.cells[at].height;
+        self.neighbours(at).all(|p| self.cells[p].height > me)
+    }
+
+    pub fn low_points(&self) -> Vec<Cell> {
+        trace!("@low_points");
+        self.cells.cells().filter(|c| self.is_low_point(c.at)).copied().collect()
+    }
+
+    // The size of the basin (not the sum of the cells within it)
+    pub fn basin_size_for_low_point(&self, c:Cell) -> usize {
+        let mut remaining_cells = self.cells.clone();
+        self.basin_explorer(c.at, &mut remaining_cells)
+    }
+
+    pub fn product_of_3_largest_basins(&self, low_points: &[Cell]) -> usize {
+        let mut basin_sizes:Vec<usize> = Vec::new();
+        
+        for cell in low_points.iter() {
+            basin_sizes.push(self.basin_size_for_low_point(*cell));
+        }
+
+        basin_sizes.sort_by(|a, b| b.cmp(a));
+
+        debug!("Sorted basin sizes: {:?}", basin_sizes);
+
+        basin_sizes[0] * basin_sizes[1] * basin_sizes[2]
+    }
+
+    // Flood each basin from its low point in turn, colouring it in, in about fifty frames
+    pub fn render(&self) -> Animation {
+        let low_points = self.low_points();
+        let per_frame = (low_points.len() / 50).max(1);
+        let mut basins: Grid<Option<usize>> = Grid::new(self.cells.width(), self.cells.height(), None);
+
+        let mut animation = Animation::new();
+        animation.push(self.frame(&basins));
+
+        for (basin, low_point) in low_points.iter().enumerate() {
+            let mut unexplored = vec![low_point.at];
+            while let Some(p) = unexplored.pop() {
+                if self.cells[p].height == 9 || basins[p].is_some() {
+                    continue;
+                }
+                basins[p] = Some(basin);
+                unexplored.extend(self.neighbours(p));
+            }
+
+            if (basin + 1) % per_frame == 0 || basin + 1 == low_points.len() {
+                animation.push(self.frame(&basins));
+            }
+        }
+
+        animation
+    }
+
+    fn frame(&self, basins: &Grid<Option<usize>>) -> Frame {
+        Frame::from_fn(self.cells.width(), self.cells.height(), |pos| basin_colour(self.cells[pos].height, basins[pos]))
+    }
+
+    // Walk through the (remaining) cells in the basin, count what can be navigated to, and return the size. The cells
+    // still to walk are kept on a stack of our own rather than recursing, as a basin can be far bigger than the call
+    // stack is deep.
+    pub fn basin_explorer(&self, at: Point, remaining_cells: &mut Grid<Cell>) -> usize {
+        let mut count = 0;
+        let mut unexplored = vec![at];
+
+        while let Some(p) = unexplored.pop() {
+            if remaining_cells[p].height == 9 {
+                continue;
+            }
+
+            count += 1;
+            remaining_cells[p].mark_as_non_walkable();
+            unexplored.extend(self.neighbours(p));
+        }
+
+        count
+    }
+}
+
+/// Walls of 9s are black and every basin gets its own colour. Anywhere not flooded yet is grey, lighter the higher up
+/// it is.
+fn basin_colour(height: usize, basin: Option<usize>) -> Rgb {
+    match (height, basin) {
+        (9, _) => BLACK,
+        (_, Some(basin)) => render::distinct(basin),
+        (height, None) => render::gradient([30, 30, 30], [150, 150, 150], height as f64 / 8.0),
+    }
+}
+
+pub fn star1(heightmap: &Heightmap) -> usize {
+    let low_points = heightmap.low_points();
+
+    let risk:usize = low_points.iter().map(|c| c.risk_level()).sum();
+
+    info!(
+        width = heightmap.cells.width(),
+        height = heightmap.cells.height(),
+        low_points = low_points.len(),
+        risk,
+        "⭐️ Analysis"
+    );
+
+    risk
+}
+
+pub fn star2(heightmap: &Heightmap) -> usize {
+    let low_points:Vec<Cell> = heightmap.low_points();
+
+    debug!("Low points: {:?}", low_points);
+
+    let answer = heightmap.product_of_3_largest_basins(&low_points);
+
+    info!(
+        width = heightmap.cells.width(),
+        height = heightmap.cells.height(),
+        low_points = low_points.len(),
+        answer,
+        "⭐️⭐️ Analysis"
+    );
+
+    answer
+}
+
+// Every basin measured in a single flood of the map, rather than copying the map for each low point to explore it. A
+// candidate to replace basin_explorer once it's proved itself.
+pub fn star2_one_flood(heightmap: &Heightmap) -> usize {
+    let mut flooded: Grid<bool> = Grid::new(heightmap.cells.width(), heightmap.cells.height(), false);
+    let mut basin_sizes: Vec<usize> = Vec::new();
+
+    for low_point in heightmap.low_points() {
+        let mut size = 0;
+        let mut unexplored = vec![low_point.at];
+        while let Some(p) = unexplored.pop() {
+            if heightmap.cells[p].height == 9 || flooded[p] {
+                continue;
+            }
+            flooded[p] = true;
+            size += 1;
+            unexplored.extend(heightmap.neighbours(p));
+        }
+        basin_sizes.push(size);
+    }
+
+    basin_sizes.sort_by(|a, b| b.cmp(a));
+    basin_sizes.iter().take(3).product()
+}
+
+/// A `size` by `size` heightmap, cut into rectangular basins by walls of 9s. Each basin slopes up from a single low
+/// point, so there's one low point per basin and every basin is exactly its rectangle. Basins get bigger with the map,
+/// up to an eighth of its side.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let size = size.max(10);
+    let span = (size / 8).max(3);
+
+    // Where each basin starts and ends along one side, leaving a wall between each
+    let cut = |rng: &mut Rng| {
+        let mut ranges = Vec::new();
+        let mut at = 0;
+        while at < size {
+            let end = (at + rng.range(1..=span)).min(size);
+            ranges.push(at..end);
+            at = end + 1;
+        }
+        ranges
+    };
+    let columns = cut(rng);
+    let rows = cut(rng);
+
+    let mut map = vec![vec![b'9'; size]; size];
+    let mut risk = 0;
+    let mut basins = Vec::new();
+    for row in &rows {
+        for column in &columns {
+            let low = (rng.range(column.start..=column.end - 1), rng.range(row.start..=row.end - 1));
+            let base = rng.range(0..=7);
+
+            for y in row.clone() {
+                for x in column.clone() {
+                    map[y][x] = b'0' + (base + x.abs_diff(low.0) + y.abs_diff(low.1)).min(8) as u8;
+                }
+            }
+
+            risk += base + 1;
+            basins.push((row.len() * column.len()) as u128);
+        }
+    }
+
+    basins.sort_by(|a, b| b.cmp(a));
+    let lines: Vec<String> = map.into_iter().map(|line| String::from_utf8(line).unwrap()).collect();
+
+    Generated {
+        input: lines.join("\n"),
+        part_1: Some(risk.into()),
+        part_2: Some((basins[0] * basins[1] * basins[2]).into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_geometry::Point;
+
+    const GIVEN_EXAMPLE: &str = aoc_core::fixture!("example");
+
+    #[test]
+    fn part_1() {
+        let heightmap = super::Heightmap::parse(GIVEN_EXAMPLE).unwrap();
+
+        // Loaded everything?
+        assert_eq!(5, heightmap.cells.height());
+        assert_eq!(10, heightmap.cells.width());
+
+        // Does our coordinate mental model match up?
+        assert_eq!(2, heightmap.cells[(0, 0)].height);
+        assert_eq!(0, heightmap.cells[(9, 0)].height);
+        assert_eq!(9, heightmap.cells[(0, 4)].height);
+        assert_eq!(8, heightmap.cells[(9, 4)].height);
+
+        // Test adjacent calculations (unneeded in part 1 but ensures sanity)
+        assert_eq!(4, heightmap.adjacent_sum(Point::new(0, 0)));
+        assert_eq!(2, heightmap.adjacent_sum(Point::new(9, 0)));
+        assert_eq!(16, heightmap.adjacent_sum(Point::new(0, 4)));
+        assert_eq!(16, heightmap.adjacent_sum(Point::new(9, 4)));
+        assert_eq!(34, heightmap.adjacent_sum(Point::new(5, 2)));
+
+        // Individual low points
+        assert!(!heightmap.is_low_point(Point::new(0, 0)));
+        assert!(heightmap.is_low_point(Point::new(1, 0)));
+        assert!(!heightmap.is_low_point(Point::new(8, 0)));
+        assert!(heightmap.is_low_point(Point::new(9, 0)));
+        assert!(!heightmap.is_low_point(Point::new(0, 4)));
+        assert!(heightmap.is_low_point(Point::new(6, 4)));
+        assert!(!heightmap.is_low_point(Point::new(9, 4)));
+
+        // Aggregate low points
+        let low_points = heightmap.low_points();
+        println!("Low points: {:?}", low_points);
+
+        assert_eq!(4, low_points.len());
+
+        // Overall risk (final answer)
+        let risk:usize = low_points.iter().map(|c| c.risk_level()).sum();
+        assert_eq!(15, risk);
+    }
+
+    const REAL_DATA_FIRST_LINE: &str = r#"8654434789432446987654321056789235678953245798764212456789656568977654232457898754567898765431335999"#;
+        
+    #[test]
+    fn p1_with_real_data_first_line() {
+        let heightmap = super::Heightmap::parse(REAL_DATA_FIRST_LINE).unwrap();
+
+        // Loaded everything?
+        assert_eq!(1, heightmap.cells.height());
+        assert_eq!(100, heightmap.cells.width());
+
+        // Does our coordinate mental model match up?
+        assert_eq!(8, heightmap.cells[(0, 0)].height);
+        assert_eq!(6, heightmap.cells[(1, 0)].height);
+        assert_eq!(9, heightmap.cells[(99, 0)].height);
+
+        // Test adjacent calculations (unneeded in part 1 but ensures sanity)
+        assert_eq!(6, heightmap.adjacent_sum(Point::new(0, 0)));
+        assert_eq!(13, heightmap.adjacent_sum(Point::new(1, 0)));
+        assert_eq!(10, heightmap.adjacent_sum(Point::new(2, 0)));
+        assert_eq!(18, heightmap.adjacent_sum(Point::new(98, 0)));
+        assert_eq!(9, heightmap.adjacent_sum(Point::new(99, 0)));
+
+        // Individual low points
+        assert!(heightmap.is_low_point(Point::new(5, 0)));
+
+        // Aggregate low points
+        let low_points = heightmap.low_points();
+        println!("Low points:\n{:?}", low_points);
+
+        // // Overall risk (final answer)
+        let risk:usize = low_points.iter().map(|c| c.risk_level()).sum();
+        println!("Aggregate risk level: {}", risk);
+    }
+
+    const REAL_DATA_SAMPLE: &str = r#"8654434789432446987654321056789235678953245798764212456789656568977654232457898754567898765431335999
+8743125678931234599987643238993123567894345989542101234678943459865432101345789643479999878540129877
+9651056799320125678998764369895644678965956976543233458799202568986574519499996532445989998761298956
+6543234789632347899219865456789795789879899898754755567893212456998986798987897621234678979872987645
+7887645696543456799423989978998989893989767789865676679995343689879497986596789432349899865989876434
+8998967789999577897999799999457678902497645699876799798789459798767329876455678943456798764699987569
+9999878899878988956986678953234567893989534578997998987689567976543213984323589894967899643209898978
+0987999999767899549875439870146789999875323466899886596578979987954201987312398789898978965799769989
+2996567899656795423964321981269893987983212345689765434499898799865332975201297598789467979987654292
+9875456998945789019875992984378942496543201456789897521345679689965449964319976465694356797998893101"#;
+        
+    #[test]
+    fn p1_with_more_real_data() {
+        let heightmap = super::Heightmap::parse(REAL_DATA_SAMPLE).unwrap();
+
+        // Loaded everything?
+        assert_eq!(10, heightmap.cells.height());
+        assert_eq!(100, heightmap.cells.width());
+
+        // Does our coordinate mental model match up?
+        assert_eq!(8, heightmap.cells[(0, 0)].height);
+        assert_eq!(9, heightmap.cells[(99, 0)].height);
+        assert_eq!(9, heightmap.cells[(0, 2)].height);
+        assert_eq!(6, heightmap.cells[(99, 2)].height);
+        assert_eq!(9, heightmap.cells[(7, 9)].height);
+
+        // Test adjacent calculations (unneeded in part 1 but ensures sanity)
+        assert_eq!(14, heightmap.adjacent_sum(Point::new(0, 0)));
+        assert_eq!(16, heightmap.adjacent_sum(Point::new(99, 0)));
+        assert_eq!(10, heightmap.adjacent_sum(Point::new(0, 9)));
+        assert_eq!(2, heightmap.adjacent_sum(Point::new(99, 9)));
+        assert_eq!(11, heightmap.adjacent_sum(Point::new(5, 2)));
+        assert_eq!(20, heightmap.adjacent_sum(Point::new(0, 7)));
+
+        // Individual low points
+        assert!(!heightmap.is_low_point(Point::new(5, 0)));
+        assert!(heightmap.is_low_point(Point::new(4, 2)));
+        assert!(heightmap.is_low_point(Point::new(0, 7)));
+
+        // Aggregate low points
+        let low_points = heightmap.low_points();
+        println!("Low points:\n{:?}", low_points);
+
+        // // Overall risk (final answer)
+        let risk:usize = low_points.iter().map(|c| c.risk_level()).sum();
+        println!("Aggregate risk level: {}", risk);
+    }
+
+    #[test]
+    fn part_2() {
+        let heightmap = super::Heightmap::parse(GIVEN_EXAMPLE).unwrap();
+        let low_points = heightmap.low_points();
+
+        assert_eq!(4, low_points.len());
+        println!("Low points: {:?}", low_points);
+
+        // Each low point has a basin...
+        assert_eq!(3, heightmap.basin_size_for_low_point(low_points[0]));
+        assert_eq!(9, heightmap.basin_size_for_low_point(low_points[1]));
+        assert_eq!(14, heightmap.basin_size_for_low_point(low_points[2]));
+        assert_eq!(9, heightmap.basin_size_for_low_point(low_points[3]));
+
+        assert_eq!(1134, heightmap.product_of_3_largest_basins(&low_points));
+    }
+
+    #[test]
+    fn part_2_debugging_wtf() {
+        const SINGLE_SMALLEST_BASIN: &str = r#"999999999
+999999999
+999999999
+999999999
+999919999
+999999999
+999999999
+999999999
+999999999"#;
+        
+        let mut heightmap = super::Heightmap::parse(SINGLE_SMALLEST_BASIN).unwrap();
+        let mut low_points = heightmap.low_points();
+
+        assert_eq!(1, low_points.len());
+        assert_eq!(1, heightmap.basin_size_for_low_point(low_points[0]));
+
+        const HIDDEN_CREVICE: &str = r#"999999999
+912992222
+992992992
+992992992
+992992992
+992992992
+992922992
+992999992
+992222222"#;
+        
+        heightmap = super::Heightmap::parse(HIDDEN_CREVICE).unwrap();
+        low_points = heightmap.low_points();
+
+        assert_eq!(1, low_points.len());
+        assert_eq!(31, heightmap.basin_size_for_low_point(low_points[0]));
+
+        const SINGLE_X_BASIN: &str = r#"799999997
+779999977
+977777779
+979979979
+977979779
+999919999
+977979779
+979979979
+977777779
+779999977
+799999997"#;
+        
+        heightmap = super::Heightmap::parse(SINGLE_X_BASIN).unwrap();
+        low_points = heightmap.low_points();
+
+        assert_eq!(1, low_points.len());
+        assert_eq!(43, heightmap.basin_size_for_low_point(low_points[0]));
+
+        const SINGLE_X_BASIN_THAT_COULD_LOOP: &str = r#"799999997
+779999977
+977777779
+979979979
+977979779
+979919979
+977979779
+979979979
+977777779
+779999977
+799999997"#;
+        
+        heightmap = super::Heightmap::parse(SINGLE_X_BASIN_THAT_COULD_LOOP).unwrap();
+        low_points = heightmap.low_points();
+
+        assert_eq!(1, low_points.len());
+        assert_eq!(45, heightmap.basin_size_for_low_point(low_points[0]));
+    }
+
+    #[test]
+    fn bad_heights() {
+        use aoc_core::ParseError;
+
+        let error = super::Heightmap::parse("2199943210\n39878?4921").unwrap_err();
+        assert_eq!(ParseError::new(2, 6, "?", "a digit"), error);
+    }
+
+    #[test]
+    fn render() {
+        use super::basin_colour;
+
+        let heightmap = super::Heightmap::parse(GIVEN_EXAMPLE).unwrap();
+        let animation = heightmap.render();
+
+        // Before anything's flooded, then each of the four basins in turn
+        assert_eq!(5, animation.frames().len());
+        assert_eq!(Some(basin_colour(2, None)), animation.frames()[0].get((0, 0)));
+
+        let last = animation.frames().last().unwrap();
+        assert_eq!(Some(basin_colour(2, Some(0))), last.get((0, 0)));
+        assert_eq!(Some(basin_colour(9, None)), last.get((2, 0)));
+        assert_eq!(Some(basin_colour(0, Some(1))), last.get((9, 0)));
+    }
+}
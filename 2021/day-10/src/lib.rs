@@ -0,0 +1,297 @@
+//! 2021 day 10: Syntax Scoring
+
+use aoc_core::{Answer, ParseError, Solution};
+use aoc_core::generate::{Generated, Rng};
+use tracing::{debug, trace};
+
+/// Day 10
+
+#[derive(Debug, PartialEq)]
+pub enum NavSubsystemLineSyntax {
+    Valid,
+    Incomplete(Vec<char>, usize),
+    Corrupted(usize),
+}
+
+pub fn parse_nav_subsystem_line(input: &str) -> Result<NavSubsystemLineSyntax, ParseError> {
+    let mut opening_chunks: Vec<char> = Vec::new();
+
+    let openers = "([{<";
+    let closers = ")]}>";
+
+    let opener_for = std::collections::HashMap::from([
+        (')', '('),
+        (']', '['),
+        ('}', '{'),
+        ('>', '<'),
+    ]);
+
+    let corruption_score = std::collections::HashMap::from([
+        (')', 3),
+        (']', 57),
+        ('}', 1197),
+        ('>', 25137),
+    ]);
+
+    trace!("🔎: {}", input);
+    for (i, c) in input.char_indices() {
+
+        if openers.contains(c) {
+            opening_chunks.push(c);
+        }
+        else if closers.contains(c) {
+            match opener_for.get(&c) {
+                Some(opener) => {
+                    trace!("Looking for {} to match closing {}", opener, c);
+                    let last = opening_chunks.pop();
+
+                    match last {
+                        Some(last) => {
+                            if last == *opener {
+                                // All good!
+                            }
+                            else {
+                                trace!("Encountered unexpected closing {} when current chunk was opened with {}.", last, opener);
+                                trace!("Looking up {} in corruption score hash map: {:?}", c, corruption_score);
+                                return Ok(NavSubsystemLineSyntax::Corrupted(*corruption_score.get(&c).unwrap()));
+                            }
+                        },
+                        None => {
+                            trace!("Encountered closing {} when no chunk opener was available.", c);
+                            return Ok(NavSubsystemLineSyntax::Corrupted(*corruption_score.get(&c).unwrap()));
+                        }
+                    }
+                },
+                None => {
+                    panic!("No opener defined for {}", c);
+                }
+            }
+        }
+        else {
+            return Err(ParseError::at(input, &input[i..i + c.len_utf8()], "one of ()[]{}<>"));
+        }
+    }
+
+    if opening_chunks.is_empty() {
+        return Ok(NavSubsystemLineSyntax::Valid)
+    }
+
+    let mut completion_score: usize = 0;
+    let mut missing_completions: Vec<char> = Vec::new();
+    let closer_for = std::collections::HashMap::from([
+        ('(', ')'),
+        ('[', ']'),
+        ('{', '}'),
+        ('<', '>'),
+    ]);
+    let completion_scores = std::collections::HashMap::from([
+        (')', 1),
+        (']', 2),
+        ('}', 3),
+        ('>', 4),
+    ]);
+
+    trace!("Incomplete line; remaining chunks to close: {:?}", opening_chunks);
+    for opener in opening_chunks.iter().rev() {
+        let required_closer = closer_for.get(opener).unwrap();
+        missing_completions.push(*required_closer);
+
+        completion_score *= 5;
+        completion_score += completion_scores.get(required_closer).unwrap();
+    }
+
+    Ok(NavSubsystemLineSyntax::Incomplete(missing_completions, completion_score))
+}
+pub struct Puzzle {
+    lines: Vec<NavSubsystemLineSyntax>,
+}
+
+impl Puzzle {
+    pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        trace!("Parsing <{}>", input);
+        let mut lines: Vec<NavSubsystemLineSyntax> = Vec::new();
+
+        for line_str in input.split("\n") {
+            let syntax = parse_nav_subsystem_line(line_str).map_err(|e| e.within(input, line_str))?;
+            lines.push(syntax);
+        }
+
+        Ok(Puzzle{ lines })
+    }
+
+    pub fn part_1(&self) -> usize {
+        let mut result:usize = 0;
+        for (i, syntax) in self.lines.iter().enumerate() {
+            if let NavSubsystemLineSyntax::Corrupted(score) = syntax {
+                debug!("Line {} is corrupted with a score of {}", i, score);
+                result += score;
+            }
+        }
+
+        result
+    }
+
+    pub fn part_2(&self) -> usize {
+        let mut autocomplete_scores:Vec<usize> = Vec::new();
+        for (i, syntax) in self.lines.iter().enumerate() {
+            if let NavSubsystemLineSyntax::Incomplete(_autocomplete, score) = syntax {
+                debug!("Line {} is incompelte with an autocorrect score of {}", i, score);
+                autocomplete_scores.push(*score);
+            }
+        }
+
+        autocomplete_scores.sort();
+
+        let middle_index = autocomplete_scores.len() / 2;
+        autocomplete_scores[middle_index]
+    }
+}
+
+pub struct Day10;
+
+aoc_core::fixture_tests!(Day10);
+
+impl Solution for Day10 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 10;
+
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::parse(input)
+    }
+
+    fn part_1(puzzle: &Puzzle) -> Answer {
+        puzzle.part_1().into()
+    }
+
+    fn part_2(puzzle: &Puzzle) -> Answer {
+        puzzle.part_2().into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+}
+
+/// `size` lines of the navigation subsystem, each either corrupted by one wrong closer (and whatever follows it) or cut
+/// off part way through, with an odd number cut off so there's a middle score. Chunks nest up to 20 deep, which keeps
+/// completion scores to 64 bits as the puzzle's are.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    const OPENERS: [char; 4] = ['(', '[', '{', '<'];
+    const CLOSERS: [char; 4] = [')', ']', '}', '>'];
+    const BRACKETS: [char; 8] = ['(', '[', '{', '<', ')', ']', '}', '>'];
+    const CORRUPTION_SCORES: [usize; 4] = [3, 57, 1197, 25137];
+
+    // A run of chunks, with at least one left open
+    let chunks = |rng: &mut Rng| {
+        let mut line = String::new();
+        let mut open: Vec<usize> = Vec::new();
+        for _ in 0..rng.range(10..=100) {
+            if open.is_empty() || (open.len() < 20 && rng.one_in(2)) {
+                let kind = rng.below(4);
+                line.push(OPENERS[kind]);
+                open.push(kind);
+            } else {
+                line.push(CLOSERS[open.pop().unwrap()]);
+            }
+        }
+        if open.is_empty() {
+            let kind = rng.below(4);
+            line.push(OPENERS[kind]);
+            open.push(kind);
+        }
+        (line, open)
+    };
+
+    let count = size.max(2);
+    let incomplete = (count / 2) | 1;
+    let mut lines = Vec::with_capacity(count);
+    let mut syntax_error_score = 0;
+    let mut completion_scores = Vec::new();
+
+    for i in 0..count {
+        let (mut line, open) = chunks(rng);
+        if i < incomplete {
+            completion_scores.push(open.iter().rev().fold(0, |score, &kind| score * 5 + kind + 1));
+        } else {
+            let expected = *open.last().unwrap();
+            let wrong = (expected + rng.range(1..=3)) % 4;
+            line.push(CLOSERS[wrong]);
+            syntax_error_score += CORRUPTION_SCORES[wrong];
+
+            for _ in 0..rng.below(10) {
+                line.push(*rng.choose(&BRACKETS));
+            }
+        }
+        lines.push(line);
+    }
+
+    rng.shuffle(&mut lines);
+    completion_scores.sort();
+
+    Generated {
+        input: lines.join("\n"),
+        part_1: Some(syntax_error_score.into()),
+        part_2: Some(completion_scores[completion_scores.len() / 2].into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = aoc_core::fixture!("example");
+
+    fn verify_syntax(input: &str, expected:NavSubsystemLineSyntax) {
+        let actual = parse_nav_subsystem_line(input).unwrap();
+
+        assert!(actual == expected, "@verify_syntax expected {:?}, was {:?}: {}", expected, actual, input);
+    }
+
+    #[test]
+    fn syntax() {
+        verify_syntax("{}", NavSubsystemLineSyntax::Valid);
+        verify_syntax("[]", NavSubsystemLineSyntax::Valid);
+        verify_syntax("([])", NavSubsystemLineSyntax::Valid);
+        verify_syntax("{()()()}", NavSubsystemLineSyntax::Valid);
+        verify_syntax("<([{}])>", NavSubsystemLineSyntax::Valid);
+        verify_syntax("[<>({}){}[([])<>]]", NavSubsystemLineSyntax::Valid);
+        verify_syntax("(((((((((())))))))))", NavSubsystemLineSyntax::Valid);
+
+        verify_syntax("(]", NavSubsystemLineSyntax::Corrupted(57));
+        verify_syntax("{()()()>", NavSubsystemLineSyntax::Corrupted(25137));
+        verify_syntax("(((()))}", NavSubsystemLineSyntax::Corrupted(1197));
+        verify_syntax("<([]){()}[{}])", NavSubsystemLineSyntax::Corrupted(3));
+
+        verify_syntax("{([(<{}[<>[]}>{[]{[(<()>", NavSubsystemLineSyntax::Corrupted(1197));
+        verify_syntax("[[<[([]))<([[{}[[()]]]", NavSubsystemLineSyntax::Corrupted(3));
+        verify_syntax("[{[{({}]{}}([{[{{{}}([]", NavSubsystemLineSyntax::Corrupted(57));
+        verify_syntax("[<(<(<(<{}))><([]([]()", NavSubsystemLineSyntax::Corrupted(3));
+        verify_syntax("<{([([[(<>()){}]>(<<{{", NavSubsystemLineSyntax::Corrupted(25137));
+
+        verify_syntax("[({(<(())[]>[[{[]{<()<>>", NavSubsystemLineSyntax::Incomplete(vec!['}', '}', ']', ']', ')', '}',')', ']'], 288957));
+        verify_syntax("[(()[<>])]({[<{<<[]>>(", NavSubsystemLineSyntax::Incomplete(vec![')','}','>',']','}',')'], 5566)); 
+        verify_syntax("(((({<>}<{<{<>}{[]{[]{}", NavSubsystemLineSyntax::Incomplete(vec!['}','}','>','}','>',')',')',')',')'], 1480781)); 
+        verify_syntax("{<[[]]>}<{[{[{[]{()[[[]", NavSubsystemLineSyntax::Incomplete(vec![']',']','}','}',']','}',']','}','>'], 995444)); 
+        verify_syntax("<{([{{}}[<[[[<>{}]]]>[]]", NavSubsystemLineSyntax::Incomplete(vec![']',')','}','>'], 294)); 
+    }
+
+    #[test]
+    fn part_1() {
+        let puzzle = super::Puzzle::parse(INPUT).unwrap();
+        assert_eq!(puzzle.part_1(), 26397);
+    }
+
+    #[test]
+    fn part_2() {
+        let puzzle = super::Puzzle::parse(INPUT).unwrap();
+        assert_eq!(puzzle.part_2(), 288957);
+    }
+
+    #[test]
+    fn bad_characters() {
+        let error = Puzzle::parse("[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<|]>>(").err();
+        assert_eq!(Some(ParseError::new(2, 18, "|", "one of ()[]{}<>")), error);
+    }
+}
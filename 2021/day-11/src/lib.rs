@@ -0,0 +1,682 @@
+//! 2021 day 11: Dumbo Octopus
+
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+
+use aoc_core::{Answer, ParseError, Solution};
+use aoc_core::animate::{paint, Colour, Screen};
+use aoc_core::generate::{Generated, Rng};
+use aoc_grid::Grid;
+use aoc_grid::render::{self, Animation, Frame, Rgb};
+use tracing::trace;
+
+/// Day 11
+
+#[derive(Clone, Copy, Debug)]
+pub struct Octopus {
+    energy: u32,
+    flashed: bool,
+}
+
+#[derive(Clone)]
+pub struct Puzzle {
+    flash_count: usize,
+    step: usize,
+    consortium: Grid<Octopus>,
+}
+
+impl Puzzle {
+    pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        trace!("Parsing <{}>", input);
+        let energy: Grid<u32> = Grid::parse_digits(input)?;
+        let consortium = energy.map(|&energy| Octopus {
+            energy,
+            flashed: false,
+        });
+
+        Ok(Puzzle {
+            flash_count: 0,
+            step: 0,
+            consortium,
+        })
+    }
+
+    // Run the flash simulation n times.
+    pub fn step(&mut self, n: usize) {
+        for _ in 0..n {
+            for octopus in self.consortium.cells_mut() {
+                octopus.energy += 1;
+            }
+
+            let mut flashed = false;
+            loop {
+                // println!("Looping for flashes");
+                for pos in self.consortium.positions() {
+                    let octopus = &mut self.consortium[pos];
+                    if octopus.energy > 9 && !octopus.flashed {
+                        // println!("   flash triggered!");
+                        flashed = true;
+                        octopus.flashed = true;
+                        self.flash_count += 1;
+                        self.increment_adjacent(pos);
+                    }
+                }
+                // println!("Finished scan. Was there a flash triggered? {}", flashed);
+                if !flashed {
+                    break;
+                }
+                flashed = false;
+            }
+
+            for octopus in self.consortium.cells_mut() {
+                if octopus.energy > 9 {
+                    octopus.energy = 0;
+                    octopus.flashed = false;
+                }
+            }
+        }
+
+        self.step += n;
+    }
+
+    fn increment_adjacent(&mut self, pos: (usize, usize)) {
+        for neighbour in self.consortium.neighbours_8(pos) {
+            self.consortium[neighbour].energy += 1;
+        }
+    }
+
+    pub fn part_1(&mut self) -> usize {
+        self.step(100);
+        self.flash_count
+    }
+
+    pub fn part_2(&mut self) -> usize {
+        loop {
+            self.step(1);
+
+            if self.synchronised() {
+                break;
+            }
+        }
+        self.step
+    }
+
+    // Did every octopus flash on the last step?
+    pub fn synchronised(&self) -> bool {
+        self.consortium.cells().all(|octopus| octopus.energy == 0)
+    }
+
+    /// One frame per step, from the start until they all flash together (or a thousand steps, if they never do)
+    pub fn render(&self) -> Animation {
+        let mut puzzle = self.clone();
+        let mut animation = Animation::new();
+        animation.push(puzzle.frame());
+
+        while puzzle.step < 1000 && !puzzle.synchronised() {
+            puzzle.step(1);
+            animation.push(puzzle.frame());
+        }
+
+        animation
+    }
+
+    /// Each step in the terminal, with the octopuses that just flashed lit up, until they all flash together
+    pub fn animate(&self, screen: &mut Screen<impl Write>) -> std::io::Result<()> {
+        let mut puzzle = self.clone();
+        screen.show(&puzzle.flashes())?;
+
+        while puzzle.step < 1000 && !puzzle.synchronised() {
+            puzzle.step(1);
+            screen.show(&puzzle.flashes())?;
+        }
+
+        Ok(())
+    }
+
+    fn flashes(&self) -> String {
+        let energy: String = self.to_string().chars().map(|c| if c == '0' { paint("0", Colour::Yellow) } else { c.to_string() }).collect();
+
+        format!("Step {}: {} flashes\n{}", self.step, self.flash_count, energy)
+    }
+
+    fn frame(&self) -> Frame {
+        Frame::of(&self.consortium, |octopus| energy_colour(octopus.energy))
+    }
+}
+
+/// Octopuses that have just flashed are bright yellow, and the rest glow brighter the closer they are to flashing
+fn energy_colour(energy: u32) -> Rgb {
+    match energy {
+        0 => [255, 240, 120],
+        energy => render::gradient([10, 15, 50], [40, 130, 170], energy as f64 / 9.0),
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.consortium.map(|octopus| octopus.energy))
+    }
+}
+
+pub struct Day11;
+
+aoc_core::fixture_tests!(Day11);
+
+impl Solution for Day11 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 11;
+
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::parse(input)
+    }
+
+    fn part_1(puzzle: &Puzzle) -> Answer {
+        puzzle.clone().part_1().into()
+    }
+
+    fn part_2(puzzle: &Puzzle) -> Answer {
+        puzzle.clone().part_2().into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+
+    fn render(puzzle: &Puzzle, to: &Path) -> Option<std::io::Result<()>> {
+        Some(puzzle.render().save(to))
+    }
+
+    fn animate(puzzle: &Puzzle, screen: &mut Screen) -> Option<std::io::Result<()>> {
+        Some(puzzle.animate(screen))
+    }
+}
+
+/// A `size` by `size` cavern of octopuses that all start with the same energy. Nothing else has answers we could know
+/// without simulating: a cavern that isn't already in step may never get there. These all flash together on the turn
+/// they reach 10, then every ten steps after, so the simulation still gets run at full size.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let size = size.max(1);
+    let energy = rng.range(0..=9);
+
+    let row = energy.to_string().repeat(size);
+    let first_flash = 10 - energy;
+
+    Generated {
+        input: vec![row; size].join("\n"),
+        part_1: Some((((100 - first_flash) / 10 + 1) * size * size).into()),
+        part_2: Some(first_flash.into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE: &str = aoc_core::fixture!("example");
+
+    fn assert_eq_energy_levels(puzzle: &Puzzle, energy_map: &str) {
+        println!("@assert_eq_energy_levels step #{}", puzzle.step);
+        let expected_puzzle = super::Puzzle::parse(energy_map).unwrap();
+
+        for ((j, i), actual) in puzzle.consortium.iter() {
+            let expected = expected_puzzle.consortium[(j, i)];
+
+            if actual.energy != expected.energy {
+                println!("💥 Disruptance in the (energy) force after step {}:\n{}", puzzle.step, puzzle);
+                panic!(
+                    "Consortium energy level mismatch at ({},{}) at step #{}. Expected {}; was {}.",
+                    i, j, puzzle.step, expected.energy, actual.energy
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tiny_5x5() {
+        let mut puzzle = super::Puzzle::parse(
+            r#"
+        11111
+        19991
+        19191
+        19991
+        11111
+    "#,
+        )
+        .unwrap();
+
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            34543
+            40004
+            50005
+            40004
+            34543
+        "#,
+        );
+
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            45654
+            51115
+            61116
+            51115
+            45654
+        "#,
+        );
+    }
+
+    #[test]
+    fn part_1() {
+        let mut puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+        assert_eq!(1656, puzzle.part_1());
+    }
+
+    #[test]
+    fn render() {
+        let puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+        let animation = puzzle.render();
+
+        // The starting state, then every step up to the first one where they all flash
+        assert_eq!(196, animation.frames().len());
+        assert_eq!(Some(energy_colour(5)), animation.frames()[0].get((0, 0)));
+        assert_eq!(Some(energy_colour(0)), animation.frames()[195].get((0, 0)));
+    }
+
+    #[test]
+    fn animate() {
+        let puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+        let mut screen = Screen::new(Vec::new(), true, true, std::time::Duration::ZERO);
+        puzzle.animate(&mut screen).unwrap();
+        let shown = String::from_utf8(screen.finish().unwrap()).unwrap();
+
+        assert_eq!(196, shown.matches("Step ").count());
+        assert!(shown.ends_with(&format!("Step 195: 3125 flashes\n{}\n", vec![paint("0", Colour::Yellow).repeat(10); 10].join("\n"))));
+    }
+
+    #[test]
+    fn part_1_baby_steps() {
+        let mut puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            6594254334
+            3856965822
+            6375667284
+            7252447257
+            7468496589
+            5278635756
+            3287952832
+            7993992245
+            5957959665
+            6394862637
+       "#,
+        );
+
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            8807476555
+            5089087054
+            8597889608
+            8485769600
+            8700908800
+            6600088989
+            6800005943
+            0000007456
+            9000000876
+            8700006848
+        "#,
+        );
+
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            0050900866
+            8500800575
+            9900000039
+            9700000041
+            9935080063
+            7712300000
+            7911250009
+            2211130000
+            0421125000
+            0021119000
+        "#,
+        );
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            2263031977
+            0923031697
+            0032221150
+            0041111163
+            0076191174
+            0053411122
+            0042361120
+            5532241122
+            1532247211
+            1132230211
+        "#,
+        );
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            4484144000
+            2044144000
+            2253333493
+            1152333274
+            1187303285
+            1164633233
+            1153472231
+            6643352233
+            2643358322
+            2243341322
+        "#,
+        );
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            5595255111
+            3155255222
+            3364444605
+            2263444496
+            2298414396
+            2275744344
+            2264583342
+            7754463344
+            3754469433
+            3354452433
+        "#,
+        );
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            6707366222
+            4377366333
+            4475555827
+            3496655709
+            3500625609
+            3509955566
+            3486694453
+            8865585555
+            4865580644
+            4465574644
+        "#,
+        );
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            7818477333
+            5488477444
+            5697666949
+            4608766830
+            4734946730
+            4740097688
+            6900007564
+            0000009666
+            8000004755
+            6800007755
+            
+        "#,
+        );
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            9060000644
+            7800000976
+            6900000080
+            5840000082
+            5858000093
+            6962400000
+            8021250009
+            2221130009
+            9111128097
+            7911119976
+        "#,
+        );
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            0481112976
+            0031112009
+            0041112504
+            0081111406
+            0099111306
+            0093511233
+            0442361130
+            5532252350
+            0532250600
+            0032240000
+        "#,
+        );
+
+        assert_eq!(204, puzzle.flash_count);
+
+        
+        puzzle.step(10);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            3936556452
+            5686556806
+            4496555690
+            4448655580
+            4456865570
+            5680086577
+            7000009896
+            0000000344
+            6000000364
+            4600009543
+        "#,
+        );
+        puzzle.step(10);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            0643334118
+            4253334611
+            3374333458
+            2225333337
+            2229333338
+            2276733333
+            2754574565
+            5544458511
+            9444447111
+            7944446119
+        "#,
+        );
+        puzzle.step(10);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            6211111981
+            0421111119
+            0042111115
+            0003111115
+            0003111116
+            0065611111
+            0532351111
+            3322234597
+            2222222976
+            2222222762
+        "#,
+        );
+        puzzle.step(10);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            9655556447
+            4865556805
+            4486555690
+            4458655580
+            4574865570
+            5700086566
+            6000009887
+            8000000533
+            6800000633
+            5680000538
+        "#,
+        );
+        puzzle.step(10);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            2533334200
+            2743334640
+            2264333458
+            2225333337
+            2225333338
+            2287833333
+            3854573455
+            1854458611
+            1175447111
+            1115446111
+        "#,
+        );
+        puzzle.step(10);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            8211111164
+            0421111166
+            0042111114
+            0004211115
+            0000211116
+            0065611111
+            0532351111
+            7322235117
+            5722223475
+            4572222754
+        "#,
+        );
+        puzzle.step(10);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            1755555697
+            5965555609
+            4486555680
+            4458655580
+            4570865570
+            5700086566
+            7000008666
+            0000000990
+            0000000800
+            0000000000
+        "#,
+        );
+        puzzle.step(10);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            7433333522
+            2643333522
+            2264333458
+            2226433337
+            2222433338
+            2287833333
+            2854573333
+            4854458333
+            3387779333
+            3333333333
+        "#,
+        );
+        puzzle.step(10);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            0397666866
+            0749766918
+            0053976933
+            0004297822
+            0004229892
+            0053222877
+            0532222966
+            9322228966
+            7922286866
+            6789998766
+        "#,
+        );
+        assert_eq!(1656, puzzle.flash_count);
+    }
+
+    #[test]
+    fn part_2() {
+        let mut puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+        assert_eq!(195, puzzle.part_2());
+    }
+
+    #[test]
+    fn part_2_validation_of_simultaneous_flash() {
+        let mut puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+
+        puzzle.step(193);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            5877777777
+            8877777777
+            7777777777
+            7777777777
+            7777777777
+            7777777777
+            7777777777
+            7777777777
+            7777777777
+            7777777777
+       "#,
+        );
+
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            6988888888
+            9988888888
+            8888888888
+            8888888888
+            8888888888
+            8888888888
+            8888888888
+            8888888888
+            8888888888
+            8888888888
+       "#,
+        );
+
+        puzzle.step(1);
+        assert_eq_energy_levels(
+            &puzzle,
+            r#"
+            0000000000
+            0000000000
+            0000000000
+            0000000000
+            0000000000
+            0000000000
+            0000000000
+            0000000000
+            0000000000
+            0000000000
+       "#,
+        );
+    }
+}
@@ -0,0 +1,454 @@
+//! 2021 day 14: Extended Polymerization
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+
+use aoc_core::{parse, Answer, ParseError, Solution};
+use aoc_core::alternative::Alternative;
+use aoc_core::animate::{self, paint, Colour, Screen};
+use aoc_core::count::{self, BigInt, Count, Counting, Overflow};
+use aoc_core::generate::{Generated, Rng};
+use tracing::debug;
+
+// Past this length we stop building the polymer itself and only track the pair counts
+const MAX_RENDERED_LENGTH: usize = 1 << 16;
+
+/// Counted in 64 bits unless told otherwise, which is enough for 40 steps but not for many more. A `Puzzle<BigInt>`
+/// counts exactly for as many steps as it's given.
+#[derive(Clone)]
+pub struct Puzzle<C: Count = u64> {
+    step: usize,
+    template: Vec<char>, // the polymer as given, to start again from
+    polymer: Vec<char>, // only kept while it's short enough to be worth printing
+    pairs: HashMap<String, char>,
+    pair_counts: HashMap<String, C>,
+    element_counts: HashMap<char, C>,
+}
+
+impl Puzzle {
+    pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        // println!("Parsing <{}>", input);
+        let (template, rules) = parse::two_sections(input, "a polymer template, a blank line, then the rules")?;
+        let polymer: Vec<char> = template.trim().chars().collect();
+
+        let pairs = parse::lines(rules, |line| match parse::rule(line, "a pair insertion rule like CH -> B")? {
+            (pair, insert) if pair.chars().count() == 2 && insert.chars().count() == 1 => {
+                Ok((pair.to_string(), insert.chars().last().unwrap()))
+            }
+            _ => Err(ParseError::at(line, line, "a pair insertion rule like CH -> B")),
+        })
+        .map_err(|e| e.within(input, rules))?;
+        let pairs: HashMap<String, char> = pairs.into_iter().collect();
+
+        Ok(Puzzle::start(polymer, pairs))
+    }
+
+    /// The same puzzle, counted some other way
+    pub fn counted<C: Count>(&self) -> Puzzle<C> {
+        Puzzle {
+            step: self.step,
+            template: self.template.clone(),
+            polymer: self.polymer.clone(),
+            pairs: self.pairs.clone(),
+            pair_counts: self.pair_counts.iter().map(|(pair, &count)| (pair.clone(), C::from_u64(count))).collect(),
+            element_counts: self.element_counts.iter().map(|(&element, &count)| (element, C::from_u64(count))).collect(),
+        }
+    }
+
+    // Part 1 the way the puzzle tells it, building the whole polymer one insertion at a time from the template. Far too
+    // slow for part 2, but kept to check the pair counting against.
+    pub fn naive_part_1(&self) -> u64 {
+        let mut polymer = self.polymer.clone();
+        for _ in 0..10 {
+            let mut next = Vec::with_capacity(polymer.len() * 2);
+            next.push(polymer[0]);
+
+            for window in polymer.windows(2) {
+                if let Some(c) = self.pairs.get(&window.iter().collect::<String>()) {
+                    next.push(*c);
+                }
+                next.push(window[1]);
+            }
+
+            polymer = next;
+        }
+
+        let mut counts: HashMap<char, u64> = HashMap::new();
+        for c in polymer {
+            *counts.entry(c).or_insert(0) += 1;
+        }
+
+        counts.values().max().unwrap() - counts.values().min().unwrap()
+    }
+}
+
+impl<C: Count> Puzzle<C> {
+    /// The puzzle before any steps have been taken
+    fn start(template: Vec<char>, pairs: HashMap<String, char>) -> Puzzle<C> {
+        // The polymer grows exponentially, but only the number of each pair matters for what gets inserted next
+        let mut pair_counts: HashMap<String, u64> = HashMap::new();
+        for window in template.windows(2) {
+            *pair_counts.entry(window.iter().collect()).or_insert(0) += 1;
+        }
+
+        let mut element_counts: HashMap<char, u64> = HashMap::new();
+        for c in template.iter() {
+            *element_counts.entry(*c).or_insert(0) += 1;
+        }
+
+        Puzzle {
+            step: 0,
+            polymer: template.clone(),
+            template,
+            pairs,
+            pair_counts: pair_counts.into_iter().map(|(pair, count)| (pair, C::from_u64(count))).collect(),
+            element_counts: element_counts.into_iter().map(|(element, count)| (element, C::from_u64(count))).collect(),
+        }
+    }
+
+    /// Stops with an error as soon as any count overflows, leaving the puzzle part way through that step.
+    pub fn step(&mut self, n:usize) -> Result<(), Overflow> {
+        for stepper in 0..n {
+            debug!("@step {} with {} kinds of pair", stepper, self.pair_counts.len());
+
+            // Each pair AB with rule AB -> C becomes AC and CB, and adds one C to the polymer
+            let mut pair_counts: HashMap<String, C> = HashMap::new();
+            for (pair, count) in self.pair_counts.iter() {
+                match self.pairs.get(pair) {
+                    Some(insert) => {
+                        let mut chars = pair.chars();
+                        let left: String = [chars.next().unwrap(), *insert].iter().collect();
+                        let right: String = [*insert, chars.next().unwrap()].iter().collect();
+
+                        pair_counts.entry(left).or_insert_with(C::zero).try_add_assign(count)?;
+                        pair_counts.entry(right).or_insert_with(C::zero).try_add_assign(count)?;
+                        self.element_counts.entry(*insert).or_insert_with(C::zero).try_add_assign(count)?;
+                    },
+                    None => {
+                        pair_counts.entry(pair.clone()).or_insert_with(C::zero).try_add_assign(count)?;
+                    },
+                }
+            }
+            self.pair_counts = pair_counts;
+
+            if self.polymer.len() * 2 > MAX_RENDERED_LENGTH {
+                self.polymer.clear();
+            }
+
+            if !self.polymer.is_empty() {
+                let mut polymer: Vec<char> = Vec::with_capacity(self.polymer.len() * 2);
+                polymer.push(self.polymer[0]);
+
+                for window in self.polymer.windows(2) {
+                    let template_pair: String = window.iter().collect();
+                    if let Some(c) = self.pairs.get(&template_pair) {
+                        polymer.push(*c);
+                    }
+                    polymer.push(window[1]);
+                }
+
+                self.polymer = polymer;
+            }
+
+            self.step += 1;
+        }
+
+        Ok(())
+    }
+
+    pub fn count(&self) -> HashMap<char, C> {
+        self.element_counts.clone()
+    }
+
+    pub fn most_minus_least_common(&self) -> Result<C, Overflow> {
+        let most_common = self.element_counts.values().max().cloned().unwrap_or_else(C::zero);
+        let least_common = self.element_counts.values().min().cloned().unwrap_or_else(C::zero);
+
+        most_common.try_sub(&least_common)
+    }
+
+    /// The most common element's count less the least common's once `steps` steps have been taken from the template,
+    /// however many this puzzle has taken already. It's left where it is.
+    pub fn most_minus_least_common_after(&self, steps: usize) -> Result<C, Overflow> {
+        let mut puzzle = match self.step {
+            step if step <= steps => self.clone(),
+            _ => Puzzle::start(self.template.clone(), self.pairs.clone()),
+        };
+
+        puzzle.step(steps - puzzle.step)?;
+        puzzle.most_minus_least_common()
+    }
+
+    pub fn part_1(&self) -> Result<C, Overflow> {
+        self.most_minus_least_common_after(10)
+    }
+
+    pub fn part_2(&self) -> Result<C, Overflow> {
+        self.most_minus_least_common_after(40)
+    }
+}
+
+impl Puzzle {
+    // Number of elements in the polymer, whether or not we're still rendering it. However many 64 bit counts there
+    // are, they add up in 128 bits.
+    fn len(&self) -> u128 {
+        self.element_counts.values().map(|&count| count as u128).sum()
+    }
+
+    // How many of each element there are, most common in green and least in red
+    pub fn element_chart(&self) -> String {
+        let mut counts: Vec<(String, u128)> = self.element_counts.iter().map(|(element, &count)| (element.to_string(), count as u128)).collect();
+        counts.sort();
+
+        let most = counts.iter().map(|&(_, count)| count).max();
+        let least = counts.iter().map(|&(_, count)| count).min();
+        let chart = animate::bars(&counts, 40);
+
+        chart
+            .lines()
+            .zip(&counts)
+            .map(|(line, &(_, count))| match Some(count) {
+                c if c == most => paint(line, Colour::Green),
+                c if c == least => paint(line, Colour::Red),
+                _ => line.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    // Every step up to part 2's, showing the polymer itself while it still fits on a line
+    pub fn animate(&self, screen: &mut Screen<impl Write>) -> std::io::Result<()> {
+        let mut puzzle = self.clone();
+
+        loop {
+            let polymer = match puzzle.to_string() {
+                polymer if polymer.len() <= 80 => polymer,
+                _ => format!("<polymer of {} elements>", puzzle.len()),
+            };
+            screen.show(&format!("Step {}: {}\n{}", puzzle.step, polymer, puzzle.element_chart()))?;
+
+            if puzzle.step >= 40 || puzzle.step(1).is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.polymer.is_empty() {
+            return write!(f, "<polymer of {} elements>", self.len());
+        }
+
+        let display: String = self.polymer.iter().collect();
+
+        write!(f, "{}", display)
+    }
+}
+
+pub struct Day14;
+
+aoc_core::fixture_tests!(Day14);
+
+impl Solution for Day14 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 14;
+
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::parse(input)
+    }
+
+    fn part_1(puzzle: &Puzzle) -> Answer {
+        count::exact_answer(|| puzzle.part_1(), || puzzle.counted::<BigInt>().part_1())
+    }
+
+    fn part_2(puzzle: &Puzzle) -> Answer {
+        count::exact_answer(|| puzzle.part_2(), || puzzle.counted::<BigInt>().part_2())
+    }
+
+    /// Part 1 takes 10 steps and part 2 takes 40, unless told otherwise
+    fn count(puzzle: &Puzzle, part: u8, counting: &Counting) -> Option<Result<Answer, Overflow>> {
+        let steps = counting.steps_or(if part == 1 { 10 } else { 40 });
+        Some(counting.answer(
+            || puzzle.most_minus_least_common_after(steps),
+            || puzzle.counted::<BigInt>().most_minus_least_common_after(steps),
+        ))
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+
+    fn animate(puzzle: &Puzzle, screen: &mut Screen) -> Option<std::io::Result<()>> {
+        Some(puzzle.animate(screen))
+    }
+
+    fn alternatives() -> Vec<Alternative<Puzzle>> {
+        vec![Alternative::new("naive", 1, |puzzle| puzzle.naive_part_1().into())]
+    }
+}
+
+/// A polymer template `size` elements long, with an insertion rule for every pair of the puzzle's ten elements. The
+/// answers are counted pair by pair as the puzzle intends, but in 128 bits: long enough templates overflow 64.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    const ELEMENTS: [char; 10] = ['B', 'C', 'F', 'H', 'K', 'N', 'O', 'P', 'S', 'V'];
+
+    let template: Vec<usize> = (0..size.max(2)).map(|_| rng.below(ELEMENTS.len())).collect();
+    let rules: Vec<Vec<usize>> = (0..ELEMENTS.len()).map(|_| (0..ELEMENTS.len()).map(|_| rng.below(ELEMENTS.len())).collect()).collect();
+
+    let spread_after = |steps: usize| -> u128 {
+        let mut elements = [0u128; ELEMENTS.len()];
+        let mut pairs = [[0u128; ELEMENTS.len()]; ELEMENTS.len()];
+        for &e in &template {
+            elements[e] += 1;
+        }
+        for pair in template.windows(2) {
+            pairs[pair[0]][pair[1]] += 1;
+        }
+
+        for _ in 0..steps {
+            let mut next = [[0u128; ELEMENTS.len()]; ELEMENTS.len()];
+            for (a, row) in pairs.iter().enumerate() {
+                for (b, &count) in row.iter().enumerate() {
+                    let insert = rules[a][b];
+                    next[a][insert] += count;
+                    next[insert][b] += count;
+                    elements[insert] += count;
+                }
+            }
+            pairs = next;
+        }
+
+        let present = elements.iter().filter(|&&n| n > 0);
+        present.clone().max().unwrap() - present.min().unwrap()
+    };
+
+    let mut input: String = template.iter().map(|&e| ELEMENTS[e]).collect();
+    input.push('\n');
+    for (a, row) in rules.iter().enumerate() {
+        for (b, &insert) in row.iter().enumerate() {
+            input.push_str(&format!("\n{}{} -> {}", ELEMENTS[a], ELEMENTS[b], ELEMENTS[insert]));
+        }
+    }
+
+    Generated {
+        input,
+        part_1: Some(spread_after(10).into()),
+        part_2: Some(spread_after(40).into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE: &str = aoc_core::fixture!("example");
+
+    #[test]
+    fn baby_steps() {
+        let mut puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+
+        assert_eq!(puzzle.polymer.len(), 4);
+        assert_eq!(puzzle.pairs.len(), 16);
+        assert_eq!(puzzle.to_string(), "NNCB");
+
+        puzzle.step(1).unwrap();
+        assert_eq!(puzzle.to_string(), "NCNBCHB");
+
+        puzzle.step(1).unwrap();
+        assert_eq!(puzzle.to_string(), "NBCCNBBBCBHCB");
+
+        puzzle.step(1).unwrap();
+        assert_eq!(puzzle.to_string(), "NBBBCNCCNBBNBNBBCHBHHBCHB");
+
+        puzzle.step(1).unwrap();
+        assert_eq!(puzzle.to_string(), "NBBNBNBBCCNBCNCCNBBNBBNBBBNBBNBBCBHCBHHNHCBBCBHCB");
+
+        puzzle.step(1).unwrap();
+        assert_eq!(puzzle.polymer.len(), 97);
+
+        puzzle.step(5).unwrap();
+        assert_eq!(puzzle.polymer.len(), 3073);
+
+        let count = puzzle.count();
+        assert_eq!(count[&'B'], 1749);
+        assert_eq!(count[&'C'], 298);
+        assert_eq!(count[&'H'], 161);
+        assert_eq!(count[&'N'], 865);
+    }
+
+    #[test]
+    fn bad_rules() {
+        let error = super::Puzzle::parse("NNCB\n\nCH -> B\nHHH -> N").err();
+        assert_eq!(Some(ParseError::new(4, 1, "HHH -> N", "a pair insertion rule like CH -> B")), error);
+    }
+
+    #[test]
+    fn part_1() {
+        let puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+        assert_eq!(1588, puzzle.part_1().unwrap());
+    }
+
+    #[test]
+    fn baby_steps_2() {
+        let mut puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+
+        // Only feasible because pairs are counted rather than the polymer being built out, which doubles every step
+        puzzle.step(40).unwrap();
+        let count = puzzle.count();
+        assert_eq!(count[&'B'], 2192039569602);
+        assert_eq!(count[&'H'], 3849876073);
+    }
+
+    #[test]
+    fn element_chart() {
+        let mut puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+        puzzle.step(10).unwrap();
+
+        assert_eq!(
+            vec![
+                paint(&format!("B {} 1749", "█".repeat(40)), Colour::Green),
+                format!("C {} 298", "█".repeat(7)),
+                paint(&format!("H {} 161", "█".repeat(4)), Colour::Red),
+                format!("N {} 865", "█".repeat(20)),
+            ],
+            puzzle.element_chart().lines().collect::<Vec<&str>>()
+        );
+    }
+
+    #[test]
+    fn part_2() {
+        let mut puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+        assert_eq!(2188189693529, puzzle.part_2().unwrap());
+
+        // Part 1 counts from the template, however far the puzzle has already been stepped
+        assert_eq!(1588, puzzle.part_1().unwrap());
+        puzzle.step(40).unwrap();
+        assert_eq!(1588, puzzle.part_1().unwrap());
+        assert_eq!(2188189693529, puzzle.part_2().unwrap());
+    }
+
+    #[test]
+    fn a_hundred_steps() {
+        let puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+        assert_eq!(Err(Overflow), puzzle.clone().step(100));
+
+        let mut puzzle = puzzle.counted::<BigInt>();
+        puzzle.step(100).unwrap();
+        assert_eq!("2535296262066596202993060773164", puzzle.most_minus_least_common().unwrap().to_string());
+    }
+
+    #[test]
+    fn counting_past_the_puzzle() {
+        let puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+        let count = |steps, big| Day14::count(&puzzle, 1, &Counting { steps, big }).unwrap();
+
+        assert_eq!(Ok(Answer::Integer(1588)), count(None, false));
+        assert_eq!(Ok(Answer::Integer(2188189693529)), count(Some(40), false));
+        assert_eq!(Err(Overflow), count(Some(100), false));
+        assert_eq!("2535296262066596202993060773164", count(Some(100), true).unwrap().to_string());
+    }
+}
@@ -0,0 +1,353 @@
+//! 2021 day 15: Chiton
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+use aoc_core::{Answer, ParseError, Solution};
+use aoc_core::alternative::Alternative;
+use aoc_core::generate::{Generated, Rng};
+use aoc_geometry::Point;
+use aoc_grid::Grid;
+use aoc_grid::render::{self, Animation, Frame, Rgb};
+
+use pathfinding::prelude::dijkstra;
+
+pub struct Puzzle {
+    map: Grid<usize>,
+}
+
+impl Puzzle {
+    pub fn parse_part1(input: &str) -> Result<Puzzle, ParseError> {
+        // println!("Parsing <{}>", input);
+        let map = Grid::parse_digits(input)?;
+        if map.width() == 0 {
+            return Err(ParseError::at(input, input, "a map of risk levels"));
+        }
+
+        Ok(Puzzle { map })
+    }
+
+    #[cfg(test)]
+    pub fn parse_part2(input: &str) -> Result<Puzzle, ParseError> {
+        Ok(Self::parse_part1(input)?.tiled())
+    }
+
+    /// The full map is the one we're given tiled 5x5, with risk going up by one (wrapping back to 1 after 9) for
+    /// every tile right or down.
+    pub fn tiled(&self) -> Puzzle {
+        let map = Grid::from_fn(self.map.width() * 5, self.map.height() * 5, |pos| self.tiled_risk(pos));
+
+        Puzzle { map }
+    }
+
+    /// The risk at a position on the full map, worked out from the map as given without building the full one, which
+    /// is 25 times the size
+    fn tiled_risk(&self, (x, y): (usize, usize)) -> usize {
+        let template_width = self.map.width();
+        let template_height = self.map.height();
+
+        let section_row = y / template_height;
+        let section_col = x / template_width;
+
+        let mut value = self.map[(x % template_width, y % template_height)] + section_row + section_col;
+        if value > 9 {
+            value %= 9;
+        }
+        value
+    }
+
+    pub fn lowest_risk(&self) -> usize {
+        lowest_risk(self.map.width(), self.map.height(), |pos| self.map[pos])
+    }
+
+    /// The route from the top left to the bottom right with the lowest total risk, along with that risk, if there's a
+    /// map to cross. This finds the path itself, for drawing, so it's much slower than [`Puzzle::lowest_risk`].
+    pub fn safest_path(&self) -> Option<(Vec<Point>, usize)> {
+        let dest = Point::from((self.map.width().checked_sub(1)?, self.map.height().checked_sub(1)?));
+        dijkstra(&Point::ORIGIN, |p| self.neighbours(p), |p| *p == dest)
+    }
+
+    /// The map with its safest path picked out
+    fn frame(&self) -> Frame {
+        let path: HashSet<Point> = self.safest_path().map(|(path, _)| path).unwrap_or_default().into_iter().collect();
+
+        Frame::from_fn(self.map.width(), self.map.height(), |pos| risk_colour(self.map[pos], path.contains(&Point::from(pos))))
+    }
+
+    /// The cave as given, then tiled out, each with the way through it
+    pub fn render(&self) -> Animation {
+        let mut animation = Animation::new();
+        animation.push(self.frame());
+        animation.push(self.tiled().frame());
+
+        animation
+    }
+
+    /// Given a position on the map, return the neighbouring cells and the required "weight" to get there.
+    pub fn neighbours(&self, pos: &Point) -> Vec<(Point, usize)> {
+        pos.neighbours_4().filter_map(|p| Some((p, *self.map.at(p)?))).collect()
+    }
+
+    pub fn part_1(&self) -> usize {
+        self.lowest_risk()
+    }
+
+    pub fn part_2(&self) -> usize {
+        lowest_risk(self.map.width() * 5, self.map.height() * 5, |pos| self.tiled_risk(pos))
+    }
+}
+
+/// The lowest total risk of any route from the top left of a `width` by `height` cave to the bottom right, entering
+/// each cell for the risk `risk` gives it. That's Dijkstra's algorithm, but as entering a cell costs 1 to 9, the cells
+/// waiting to be visited are never more than nine apart: a bucket for each of the ten totals they could have makes a
+/// queue (Dial's algorithm), and with the best totals so far kept in a grid, a cave of a hundred million cells fits.
+fn lowest_risk(width: usize, height: usize, risk: impl Fn((usize, usize)) -> usize) -> usize {
+    let destination = (width - 1, height - 1);
+    let mut best: Grid<u32> = Grid::new(width, height, u32::MAX);
+    let mut buckets: [Vec<(usize, usize)>; 10] = Default::default();
+
+    best[(0, 0)] = 0;
+    buckets[0].push((0, 0));
+
+    let mut total = 0;
+    while buckets.iter().any(|bucket| !bucket.is_empty()) {
+        while let Some(pos) = buckets[total % 10].pop() {
+            // Already reached for less since it was queued
+            if best[pos] < total as u32 {
+                continue;
+            }
+            if pos == destination {
+                return total;
+            }
+
+            let (x, y) = pos;
+            for next in [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)] {
+                if !best.contains(next) {
+                    continue;
+                }
+
+                let next_total = total + risk(next);
+                if (next_total as u32) < best[next] {
+                    best[next] = next_total as u32;
+                    buckets[next_total % 10].push(next);
+                }
+            }
+        }
+        total += 1;
+    }
+
+    unreachable!("Every cell of a cave can be reached from the top left")
+}
+
+/// Risky cells are darker. The path through is lit up in red, brighter where it's forced through more risk.
+fn risk_colour(risk: usize, on_path: bool) -> Rgb {
+    let t = (risk as f64 - 1.0) / 8.0;
+    if on_path {
+        render::gradient([255, 120, 100], [200, 0, 0], t)
+    } else {
+        render::gradient([200, 220, 200], [20, 60, 30], t)
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.map)
+    }
+}
+
+pub struct Day15;
+
+aoc_core::fixture_tests!(Day15);
+
+impl Solution for Day15 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 15;
+
+    /// The map as given. Part 2 tiles it out to its full size itself.
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::parse_part1(input)
+    }
+
+    fn part_1(puzzle: &Puzzle) -> Answer {
+        puzzle.part_1().into()
+    }
+
+    fn part_2(puzzle: &Puzzle) -> Answer {
+        puzzle.part_2().into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+
+    fn render(puzzle: &Puzzle, to: &Path) -> Option<std::io::Result<()>> {
+        Some(puzzle.render().save(to))
+    }
+
+    fn alternatives() -> Vec<Alternative<Puzzle>> {
+        vec![
+            Alternative::new("pathfinding crate", 1, safest_risk),
+            Alternative::new("pathfinding crate", 2, |puzzle| safest_risk(&puzzle.tiled())),
+        ]
+    }
+}
+
+/// The total risk along the safest path, found with the pathfinding crate
+fn safest_risk(puzzle: &Puzzle) -> Answer {
+    puzzle.safest_path().map_or_else(|| Answer::Text("no way through".to_string()), |(_, risk)| risk.into())
+}
+
+/// A `size` by `size` cave of random risks with a winding path of 1s from corner to corner. Every step costs at least 1
+/// and no route gets there in fewer steps than that path, so it's the safest. There's no such shortcut through the
+/// tiled-out cave, whose risks all shift, so part 2 has no known answer.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let size = size.max(2);
+
+    let mut cave: Vec<Vec<u8>> = (0..size).map(|_| (0..size).map(|_| b'0' + rng.range(1..=9) as u8).collect()).collect();
+    let (mut x, mut y) = (0, 0);
+    cave[0][0] = b'1';
+    while (x, y) != (size - 1, size - 1) {
+        if y == size - 1 || (x < size - 1 && rng.one_in(2)) {
+            x += 1;
+        } else {
+            y += 1;
+        }
+        cave[y][x] = b'1';
+    }
+
+    let lines: Vec<String> = cave.into_iter().map(|row| String::from_utf8(row).unwrap()).collect();
+
+    Generated {
+        input: lines.join("\n"),
+        part_1: Some((2 * (size - 1)).into()),
+        part_2: None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE: &str = aoc_core::fixture!("example");
+
+    #[test]
+    fn baby_steps() {
+        let mut puzzle = super::Puzzle::parse_part1(
+            r#"
+            11
+            91
+        "#,
+        )
+        .unwrap();
+        assert_eq!(2, puzzle.lowest_risk());
+
+        puzzle = super::Puzzle::parse_part1(
+            r#"
+            19
+            11
+        "#,
+        )
+        .unwrap();
+        assert_eq!(2, puzzle.lowest_risk());
+
+        puzzle = super::Puzzle::parse_part1(
+            r#"
+            129
+            129
+            839
+        "#,
+        )
+        .unwrap();
+        assert_eq!(15, puzzle.lowest_risk());
+
+        puzzle = super::Puzzle::parse_part1(
+            r#"
+            111
+            275
+            839
+        "#,
+        )
+        .unwrap();
+        assert_eq!(16, puzzle.lowest_risk());
+
+        assert_eq!(2, puzzle.neighbours(&Point::new(0, 0)).len());
+        assert_eq!(2, puzzle.neighbours(&Point::new(2, 0)).len());
+        assert_eq!(2, puzzle.neighbours(&Point::new(0, 2)).len());
+        assert_eq!(2, puzzle.neighbours(&Point::new(2, 2)).len());
+        assert_eq!(4, puzzle.neighbours(&Point::new(1, 1)).len());
+
+        assert_eq!(
+            3,
+            puzzle
+                .neighbours(&Point::new(0, 0))
+                .iter()
+                .fold(0, |acc, n| acc + n.1)
+        );
+
+        assert_eq!(
+            11,
+            puzzle
+                .neighbours(&Point::new(1, 1))
+                .iter()
+                .fold(0, |acc, n| acc + n.1)
+        );
+
+        assert_eq!(
+            8,
+            puzzle
+                .neighbours(&Point::new(2, 2))
+                .iter()
+                .fold(0, |acc, n| acc + n.1)
+        );
+
+        puzzle = super::Puzzle::parse_part1(
+            r#"
+            19999
+            19111
+            11191"#,
+        )
+        .unwrap();
+        assert_eq!(8, puzzle.lowest_risk());
+    }
+
+    #[test]
+    fn part_1() {
+        let puzzle = super::Puzzle::parse_part1(SAMPLE).unwrap();
+
+        assert_eq!(puzzle.map.height(), 10);
+        assert_eq!(40, puzzle.part_1());
+    }
+
+    #[test]
+    fn part_2() {
+        let puzzle = super::Puzzle::parse_part2(SAMPLE).unwrap();
+
+        assert_eq!(puzzle.map.height(), 10 * 5); // 5 times larger in both dimensions
+        assert_eq!(puzzle.map.width(), 10 * 5);
+        assert_eq!(315, puzzle.lowest_risk());
+    }
+
+    #[test]
+    fn bad_maps() {
+        assert_eq!(Some(ParseError::new(1, 1, "", "a map of risk levels")), super::Puzzle::parse_part1("").err());
+        assert_eq!(Some(ParseError::new(2, 2, "x", "a digit")), super::Puzzle::parse_part1("11\n1x").err());
+        assert_eq!(None, Puzzle { map: Grid::new(0, 0, 0) }.safest_path());
+    }
+
+    #[test]
+    fn render() {
+        let puzzle = super::Puzzle::parse_part1(SAMPLE).unwrap();
+        let frames = puzzle.render().frames().to_vec();
+
+        assert_eq!(vec![(10, 10), (50, 50)], frames.iter().map(|f| (f.width(), f.height())).collect::<Vec<_>>());
+
+        // The sample's path runs down the left edge to start with, and never touches the top right corner
+        assert_eq!(Some(risk_colour(1, true)), frames[0].get((0, 0)));
+        assert_eq!(Some(risk_colour(2, true)), frames[0].get((0, 2)));
+        assert_eq!(Some(risk_colour(2, false)), frames[0].get((9, 0)));
+        assert_eq!(Some(risk_colour(9, true)), frames[1].get((49, 49)));
+    }
+}
@@ -0,0 +1,236 @@
+//! 2022 day 1: Calorie Counting
+
+use std::fmt;
+use std::io::BufRead;
+
+use aoc_core::{parse, stream, Answer, ParseError, Solution};
+use aoc_core::generate::{Generated, Rng};
+use aoc_core::stream::StreamError;
+use tracing::trace;
+
+pub struct Puzzle {
+    elves: Vec<Elf>,
+}
+
+#[derive(Clone)]
+pub struct Elf {
+    inventory: Vec<usize>,
+    total_calories: usize,
+}
+
+impl Puzzle {
+    pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        trace!("Parsing <{}>", input);
+        // Each Elf's items are a section of their own, with a blank line before the next Elf's
+        let mut elves: Vec<Elf> = Vec::new();
+        for section in parse::sections(input) {
+            let inventory: Vec<usize> = parse::lines(section, |line| aoc_core::parse_number(line, line))
+                .map_err(|e| e.within(input, section))?;
+            let total_calories = inventory.iter().sum::<usize>();
+            elves.push(Elf {
+                inventory,
+                total_calories,
+            });
+        }
+
+        Ok(Puzzle { elves })
+    }
+
+    pub fn part_1(&self) -> usize {
+        let mut max_calories: usize = 0;
+
+        for (i, elf) in self.elves.iter().enumerate() {
+            trace!(
+                "Elf #{} has {} items with a total of {} calories",
+                i,
+                elf.inventory.len(),
+                elf.total_calories
+            );
+
+            if elf.total_calories > max_calories {
+                max_calories = elf.total_calories;
+            }
+        }
+
+        max_calories
+    }
+
+    pub fn part_2(&self) -> usize {
+        // Sort elves by the total calories they are carrying, *ascending*
+        let mut sorted_elves = self.elves.clone();
+        sorted_elves.sort_by_key(|e| e.total_calories);
+
+        if sorted_elves.len() < 3 {
+            panic!("Not enough elves to find the top 3");
+        }
+
+        sorted_elves[sorted_elves.len() - 3..sorted_elves.len()]
+            .iter()
+            .map(|e| e.total_calories)
+            .sum::<usize>()
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut display = "".to_string();
+
+        for elf in self.elves.iter() {
+            display.push_str(" * ");
+            display.push_str(&elf.to_string());
+        }
+
+        write!(f, "{}", display)
+    }
+}
+
+impl fmt::Display for Elf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut display = "".to_string();
+
+        for item in self.inventory.iter() {
+            display.push_str(&item.to_string());
+            display.push(',');
+        }
+
+        write!(f, "{}", display)
+    }
+}
+
+pub struct Day01;
+
+aoc_core::fixture_tests!(Day01);
+
+impl Solution for Day01 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 1;
+
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::parse(input)
+    }
+
+    fn part_1(puzzle: &Puzzle) -> Answer {
+        puzzle.part_1().into()
+    }
+
+    fn part_2(puzzle: &Puzzle) -> Answer {
+        puzzle.part_2().into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+
+    fn stream(reader: &mut dyn BufRead) -> Option<Result<[Answer; 2], StreamError>> {
+        Some(stream(reader).map(|(most, top_three)| [most.into(), top_three.into()]))
+    }
+}
+
+/// Both answers in one pass, keeping only the Elf being counted and the three carrying the most so far.
+pub fn stream(reader: impl BufRead) -> Result<(usize, usize), StreamError> {
+    let mut top_three = [0; 3]; // most first
+    let mut elves = 0;
+    let mut carrying = None;
+
+    let mut done = |total: usize| {
+        trace!("Elf #{} has a total of {} calories", elves, total);
+        elves += 1;
+
+        if let Some(i) = top_three.iter().position(|&top| total > top) {
+            top_three[i..].rotate_right(1);
+            top_three[i] = total;
+        }
+    };
+
+    stream::lines_with_blanks(reader, |line| {
+        match (line.trim(), carrying) {
+            ("", Some(total)) => {
+                done(total);
+                carrying = None;
+            }
+            ("", None) => (),
+            (item, total) => {
+                let calories: usize = aoc_core::parse_number(line, item)?;
+                carrying = Some(total.unwrap_or(0) + calories);
+            }
+        }
+
+        Ok(())
+    })?;
+    if let Some(total) = carrying {
+        done(total);
+    }
+
+    if elves < 3 {
+        panic!("Not enough elves to find the top 3");
+    }
+
+    Ok((top_three[0], top_three.iter().sum()))
+}
+
+/// `size` elves (at least three, for part 2), each carrying up to fifteen snacks
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let elves: Vec<Vec<usize>> = (0..size.max(3)).map(|_| (0..rng.range(1..=15)).map(|_| rng.range(1000..=60000)).collect()).collect();
+
+    let mut totals: Vec<usize> = elves.iter().map(|snacks| snacks.iter().sum()).collect();
+    totals.sort_by(|a, b| b.cmp(a));
+
+    let input = elves
+        .iter()
+        .map(|snacks| snacks.iter().map(|s| s.to_string()).collect::<Vec<String>>().join("\n"))
+        .collect::<Vec<String>>()
+        .join("\n\n");
+
+    Generated {
+        input,
+        part_1: Some(totals[0].into()),
+        part_2: Some(totals[..3].iter().sum::<usize>().into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE: &str = aoc_core::fixture!("example");
+
+    #[test]
+    fn baby_steps() {
+        let puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+
+        assert_eq!(puzzle.elves.len(), 5);
+        assert_eq!(puzzle.elves[0].inventory.len(), 3);
+        assert_eq!(puzzle.elves[1].inventory.len(), 1);
+        assert_eq!(puzzle.elves[1].inventory[0], 4000);
+    }
+
+    #[test]
+    fn part_1() {
+        let puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+        assert_eq!(24000, puzzle.part_1());
+    }
+
+    #[test]
+    fn part_2() {
+        let puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+        assert_eq!(45000, puzzle.part_2());
+    }
+
+    #[test]
+    fn bad_calories() {
+        let error = super::Puzzle::parse("1000\n2000\n\n3OOO\n").err();
+        assert_eq!(Some(ParseError::new(4, 1, "3OOO", "a number")), error);
+    }
+
+    #[test]
+    fn streamed() {
+        assert_eq!((24000, 45000), stream(SAMPLE.as_bytes()).unwrap());
+
+        match stream("1000\n2000\n\n3OOO\n".as_bytes()) {
+            Err(StreamError::Parse(e)) => assert_eq!(ParseError::new(4, 1, "3OOO", "a number"), e),
+            other => panic!("Expected a parse error, not {:?}", other),
+        }
+    }
+}
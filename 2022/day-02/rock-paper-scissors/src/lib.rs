@@ -0,0 +1,297 @@
+//! 2022 day 2: Rock Paper Scissors
+
+use std::io::BufRead;
+use std::str::FromStr;
+
+use aoc_core::{stream, Answer, ParseError, Solution};
+use aoc_core::generate::{Generated, Rng};
+use aoc_core::stream::StreamError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Choice {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ending {
+    Win,
+    Lose,
+    Draw,
+}
+
+// How to interpret the X, Y, Z encoding within the input
+pub enum Encoding {
+    WhatToPlay, // star 1
+    HowItEnds,  // star 2
+}
+
+pub struct Tournament {
+    rounds: Vec<Round>,
+}
+
+#[derive(Clone)]
+pub struct Round {
+    theirs: Choice,
+    mine: Choice,
+}
+
+impl FromStr for Choice {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Choice, Self::Err> {
+        match input {
+            "A" => Ok(Choice::Rock),
+            "B" => Ok(Choice::Paper),
+            "C" => Ok(Choice::Scissors),
+            "X" => Ok(Choice::Rock),
+            "Y" => Ok(Choice::Paper),
+            "Z" => Ok(Choice::Scissors),
+            _ => Err(ParseError::at(input, input, "one of A, B, C, X, Y or Z")),
+        }
+    }
+}
+
+impl FromStr for Ending {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Ending, Self::Err> {
+        match input {
+            "X" => Ok(Ending::Lose),
+            "Y" => Ok(Ending::Draw),
+            "Z" => Ok(Ending::Win),
+            _ => Err(ParseError::at(input, input, "one of X, Y or Z")),
+        }
+    }
+}
+
+pub fn winning_play(theirs: &Choice) -> Choice {
+    match theirs {
+        Choice::Rock => Choice::Paper,
+        Choice::Paper => Choice::Scissors,
+        Choice::Scissors => Choice::Rock,
+    }
+}
+
+pub fn losing_play(theirs: &Choice) -> Choice {
+    match theirs {
+        Choice::Rock => Choice::Scissors,
+        Choice::Paper => Choice::Rock,
+        Choice::Scissors => Choice::Paper,
+    }
+}
+
+pub fn drawing_play(theirs: &Choice) -> Choice {
+    match theirs {
+        Choice::Rock => Choice::Rock,
+        Choice::Paper => Choice::Paper,
+        Choice::Scissors => Choice::Scissors,
+    }
+}
+
+pub fn parse_choices(input: &str, mode: &Encoding) -> Result<(Choice, Choice), ParseError> {
+    let (their_str, my_str) = input
+        .split_once(' ')
+        .ok_or_else(|| ParseError::at(input, input, "two choices like A Y"))?;
+    let theirs = Choice::from_str(their_str).map_err(|e| e.within(input, their_str))?;
+
+    match mode {
+        Encoding::WhatToPlay => {
+            let mine = Choice::from_str(my_str).map_err(|e| e.within(input, my_str))?;
+            Ok((theirs, mine))
+        }
+        Encoding::HowItEnds => {
+            let ending = Ending::from_str(my_str).map_err(|e| e.within(input, my_str))?;
+            let mine = match ending {
+                Ending::Win => winning_play(&theirs),
+                Ending::Lose => losing_play(&theirs),
+                Ending::Draw => drawing_play(&theirs),
+            };
+            Ok((theirs, mine))
+        }
+    }
+}
+
+impl Tournament {
+    pub fn parse(input: &str, mode: Encoding) -> Result<Tournament, ParseError> {
+        //println!("Parsing <{}>", input);
+        let mut rounds: Vec<Round> = Vec::new();
+
+        for line_str in input.trim().split("\n") {
+            let _trimmed = line_str.trim();
+
+            let (theirs, mine) = parse_choices(line_str, &mode).map_err(|e| e.within(input, line_str))?;
+
+            rounds.push(Round { theirs, mine });
+        }
+
+        Ok(Tournament { rounds })
+    }
+
+    pub fn part_1(&self) -> usize {
+        self.rounds.iter().map(|r| r.score()).sum()
+    }
+
+    pub fn part_2(&self) -> usize {
+        self.rounds.iter().map(|r| r.score()).sum()
+    }
+}
+
+impl Round {
+    pub fn score(&self) -> usize {
+        let choice_score = match self.mine {
+            Choice::Rock => 1,
+            Choice::Paper => 2,
+            Choice::Scissors => 3,
+        };
+
+        let win_score = match self.mine {
+            Choice::Rock => {
+                match self.theirs {
+                    Choice::Rock => 3,     // tie
+                    Choice::Paper => 0,    // they won
+                    Choice::Scissors => 6, // I won
+                }
+            }
+            Choice::Paper => {
+                match self.theirs {
+                    Choice::Rock => 6,     // I win
+                    Choice::Paper => 3,    // Tie
+                    Choice::Scissors => 0, // I lost!
+                }
+            }
+            Choice::Scissors => {
+                match self.theirs {
+                    Choice::Rock => 0,     // I lose
+                    Choice::Paper => 6,    // I win!
+                    Choice::Scissors => 3, // Tie
+                }
+            }
+        };
+
+        choice_score + win_score
+    }
+}
+
+pub struct Day02;
+
+aoc_core::fixture_tests!(Day02);
+
+impl Solution for Day02 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 2;
+
+    /// The strategy guide read both ways: as what to play (part 1), and as how each round should end (part 2)
+    type Input = (Tournament, Tournament);
+
+    fn parse(input: &str) -> Result<(Tournament, Tournament), ParseError> {
+        Ok((
+            Tournament::parse(input, Encoding::WhatToPlay)?,
+            Tournament::parse(input, Encoding::HowItEnds)?,
+        ))
+    }
+
+    fn part_1((tourney, _): &(Tournament, Tournament)) -> Answer {
+        tourney.part_1().into()
+    }
+
+    fn part_2((_, tourney): &(Tournament, Tournament)) -> Answer {
+        tourney.part_2().into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+
+    fn stream(reader: &mut dyn BufRead) -> Option<Result<[Answer; 2], StreamError>> {
+        Some(stream(reader).map(|(what_to_play, how_it_ends)| [what_to_play.into(), how_it_ends.into()]))
+    }
+}
+
+/// Both answers in one pass, scoring each round both ways as it's read rather than keeping it.
+pub fn stream(reader: impl BufRead) -> Result<(usize, usize), StreamError> {
+    let mut scores = (0, 0);
+
+    stream::lines(reader, |line| {
+        for (mode, score) in [(Encoding::WhatToPlay, &mut scores.0), (Encoding::HowItEnds, &mut scores.1)] {
+            let (theirs, mine) = parse_choices(line, &mode)?;
+            *score += Round { theirs, mine }.score();
+        }
+
+        Ok(())
+    })?;
+
+    Ok(scores)
+}
+
+/// `size` rounds, scored straight from the letters: for each shape 0 to 2, the next one along beats it.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let rounds: Vec<(usize, usize)> = (0..size.max(1)).map(|_| (rng.below(3), rng.below(3))).collect();
+
+    // As shapes, X is what we play; as endings, it's lose, draw or win
+    let as_shapes: usize = rounds.iter().map(|&(theirs, mine)| mine + 1 + 3 * ((mine + 4 - theirs) % 3)).sum();
+    let as_endings: usize = rounds.iter().map(|&(theirs, ending)| (theirs + ending + 2) % 3 + 1 + 3 * ending).sum();
+
+    Generated {
+        input: rounds.iter().map(|&(theirs, mine)| format!("{} {}", ['A', 'B', 'C'][theirs], ['X', 'Y', 'Z'][mine])).collect::<Vec<String>>().join("\n"),
+        part_1: Some(as_shapes.into()),
+        part_2: Some(as_endings.into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE: &str = aoc_core::fixture!("example");
+
+    #[test]
+    fn baby_steps() {
+        let tourney = super::Tournament::parse(SAMPLE, Encoding::WhatToPlay).unwrap();
+
+        assert_eq!(tourney.rounds.len(), 3);
+        assert_eq!(tourney.rounds[0].theirs, Choice::Rock);
+        assert_eq!(tourney.rounds[0].mine, Choice::Paper);
+        assert_eq!(tourney.rounds[0].score(), 8);
+
+        assert_eq!(tourney.rounds[1].theirs, Choice::Paper);
+        assert_eq!(tourney.rounds[1].mine, Choice::Rock);
+        assert_eq!(tourney.rounds[1].score(), 1);
+
+        assert_eq!(tourney.rounds[2].theirs, Choice::Scissors);
+        assert_eq!(tourney.rounds[2].mine, Choice::Scissors);
+        assert_eq!(tourney.rounds[2].score(), 6);
+    }
+
+    #[test]
+    fn part_1() {
+        let tourney = super::Tournament::parse(SAMPLE, Encoding::WhatToPlay).unwrap();
+        assert_eq!(15, tourney.part_1());
+    }
+
+    #[test]
+    fn part_2() {
+        let tourney = super::Tournament::parse(SAMPLE, Encoding::HowItEnds).unwrap();
+        assert_eq!(12, tourney.part_2());
+    }
+
+    #[test]
+    fn bad_rounds() {
+        let error = Day02::parse("A Y\nB W\n").err();
+        assert_eq!(Some(ParseError::new(2, 3, "W", "one of A, B, C, X, Y or Z")), error);
+
+        let error = Day02::parse("A Y\nBX\n").err();
+        assert_eq!(Some(ParseError::new(2, 1, "BX", "two choices like A Y")), error);
+    }
+
+    #[test]
+    fn streamed() {
+        assert_eq!((15, 12), stream(SAMPLE.as_bytes()).unwrap());
+
+        match stream("A Y\nB W\n".as_bytes()) {
+            Err(StreamError::Parse(e)) => assert_eq!(ParseError::new(2, 3, "W", "one of A, B, C, X, Y or Z"), e),
+            other => panic!("Expected a parse error, not {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+fn main() {
+    aoc_core::fixture::generate_tests();
+}
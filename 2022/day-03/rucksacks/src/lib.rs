@@ -0,0 +1,276 @@
+//! 2022 day 3: Rucksack Reorganization
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use aoc_core::{Answer, ParseError, Solution};
+use aoc_core::generate::{Generated, Rng};
+
+pub struct Puzzle {
+    rucksacks: Vec<Rucksack>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Rucksack {
+    comparment_1: HashMap<char, usize>,
+    comparment_2: HashMap<char, usize>,
+    all_items: HashSet<char>,
+}
+
+// todo someday maybe
+// type Priority = usize;
+// impl From<char> for Priority {
+//     fn from(c: char) -> Self {
+//         ...
+//     }
+// }
+
+/// Items in the first compartment, the second, and both together
+pub type Items = (Vec<char>, Vec<char>, Vec<char>);
+
+pub fn parse_items(input: &str) -> Result<Items, ParseError> {
+    if let Some((i, c)) = input.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        return Err(ParseError::at(input, &input[i..i + c.len_utf8()], "an item from a to z or A to Z"));
+    }
+    if !input.len().is_multiple_of(2) {
+        return Err(ParseError::at(input, input, "an even number of items, to split between both compartments"));
+    }
+
+    let parts = input.chars();
+    let mut items = VecDeque::from_iter(parts);
+
+    let mut left: Vec<char> = Vec::new();
+    let mut right: Vec<char> = Vec::new();
+
+    while !items.is_empty() {
+        left.push(items.pop_front().unwrap());
+        right.push(items.pop_back().unwrap());
+    }
+    Ok((
+        left.clone(),
+        right.clone(),
+        left.into_iter().chain(right).collect(),
+    ))
+}
+
+impl Puzzle {
+    pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        //println!("Parsing <{}>", input);
+        let mut rucksacks: Vec<Rucksack> = Vec::new();
+
+        for line_str in input.trim().split("\n") {
+            let _trimmed = line_str.trim();
+
+            let (items1, items2, all) = parse_items(line_str).map_err(|e| e.within(input, line_str))?;
+
+            let coalesced_items1 = items1.into_iter().fold(HashMap::new(), |mut acc, c| {
+                *acc.entry(c).or_insert(0) += 1;
+                acc
+            });
+
+            let coalesced_items2 = items2.into_iter().fold(HashMap::new(), |mut acc, c| {
+                *acc.entry(c).or_insert(0) += 1;
+                acc
+            });
+
+            rucksacks.push(Rucksack {
+                comparment_1: coalesced_items1,
+                comparment_2: coalesced_items2,
+                all_items: all.into_iter().collect::<HashSet<char>>(),
+            });
+        }
+
+        Ok(Puzzle { rucksacks })
+    }
+
+    pub fn priorty_sum(&self) -> usize {
+        self.rucksacks
+            .iter()
+            .fold(0, |acc, r| acc + r.duplicate().1)
+    }
+
+    pub fn item_in_common(&self, rucksacks: &[Rucksack]) -> (char, usize) {
+        let overlap = rucksacks[0].all_items.intersection(&rucksacks[1].all_items);
+
+        for c in overlap.into_iter() {
+            if rucksacks[2].all_items.contains(c) {
+                return (*c, rucksacks[2].priority(*c));
+            }
+        }
+
+        panic!("There was no item in common for this group of rucksacks");
+    }
+
+    pub fn group_badge_priority_sum(&self) -> usize {
+        let mut sum = 0;
+
+        for group in self.rucksacks.chunks_exact(3) {
+            let (_, priority) = self.item_in_common(group);
+            sum += priority;
+        }
+
+        sum
+    }
+
+    pub fn part_1(&self) -> usize {
+        self.priorty_sum()
+    }
+
+    pub fn part_2(&self) -> usize {
+        self.group_badge_priority_sum()
+    }
+}
+
+impl Rucksack {
+    pub fn duplicate(&self) -> (char, usize) {
+        // The Elf that did the packing failed to follow this rule for exactly one item type per rucksack.
+        let mut dupe: Option<char> = None;
+        for k in self.comparment_1.keys() {
+            if self.comparment_2.contains_key(k) {
+                dupe = Some(*k);
+            }
+        }
+
+        let d = dupe.unwrap();
+        (d, self.priority(d))
+    }
+
+    pub fn priority(&self, c: char) -> usize {
+        // Lowercase item types a through z have priorities 1 through 26.
+        // Uppercase item types A through Z have priorities 27 through 52.
+        let ascii = c as usize;
+
+        if ascii >= 97 {
+            ascii - 96 // a = 1
+        } else {
+            ascii - 38 // A == 27
+        }
+    }
+}
+
+pub struct Day03;
+
+aoc_core::fixture_tests!(Day03);
+
+impl Solution for Day03 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 3;
+
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        Puzzle::parse(input)
+    }
+
+    fn part_1(puzzle: &Puzzle) -> Answer {
+        puzzle.part_1().into()
+    }
+
+    fn part_2(puzzle: &Puzzle) -> Answer {
+        puzzle.part_2().into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        Some(generate(size, rng))
+    }
+}
+
+/// `size` rucksacks, rounded up to whole groups of three. Each group splits the item types between its rucksacks so the
+/// badge is the only type all three carry, and each rucksack splits its share between its compartments so exactly one
+/// type turns up in both.
+fn generate(size: usize, rng: &mut Rng) -> Generated {
+    let priority = |item: u8| if item.is_ascii_lowercase() { (item - b'a' + 1) as usize } else { (item - b'A' + 27) as usize };
+    let mut types: Vec<u8> = (b'a'..=b'z').chain(b'A'..=b'Z').collect();
+
+    let mut lines = Vec::new();
+    let (mut misplaced, mut badges) = (0, 0);
+    for _ in 0..size.max(1).div_ceil(3) {
+        rng.shuffle(&mut types);
+        let badge = types[0];
+        badges += priority(badge);
+
+        for share in types[1..].chunks(17) {
+            // The type in both compartments, which might be the badge
+            let both = if rng.one_in(3) { badge } else { share[0] };
+            misplaced += priority(both);
+
+            let others: Vec<u8> = share.iter().copied().filter(|&t| t != both).collect();
+            let (left, right) = others.split_at(others.len() / 2);
+            let badge_left = rng.one_in(2);
+
+            let length = rng.range(4..=20);
+            let mut compartments = [vec![both], vec![both]];
+            if both != badge {
+                compartments[if badge_left { 0 } else { 1 }].push(badge);
+            }
+            for (compartment, pool) in compartments.iter_mut().zip([left, right]) {
+                while compartment.len() < length {
+                    compartment.push(*rng.choose(pool));
+                }
+                rng.shuffle(compartment);
+            }
+
+            lines.push(String::from_utf8([compartments[0].clone(), compartments[1].clone()].concat()).unwrap());
+        }
+    }
+
+    Generated {
+        input: lines.join("\n"),
+        part_1: Some(misplaced.into()),
+        part_2: Some(badges.into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE: &str = aoc_core::fixture!("example");
+
+    #[test]
+    fn baby_steps() {
+        let puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+
+        assert_eq!(puzzle.rucksacks.len(), 6);
+
+        // First rucksack has items vJrwpWtwJgWrhcsFMMfFFhFp stored in:
+        //   compartment 1 vJrwpWtwJgWr -> v,J2,r2,w2,p,W2,t,g
+        //   compartment 2 hcsFMMfFFhFp -> h2,c,s,F4,M2,f,p
+        assert_eq!(puzzle.rucksacks[0].comparment_1.len(), 8);
+        assert_eq!(puzzle.rucksacks[0].comparment_2.len(), 7);
+
+        assert_eq!(puzzle.rucksacks[0].comparment_1[&'v'], 1);
+        assert_eq!(puzzle.rucksacks[0].comparment_1[&'p'], 1);
+        assert_eq!(puzzle.rucksacks[0].comparment_2[&'F'], 4);
+        assert_eq!(puzzle.rucksacks[0].comparment_2[&'p'], 1);
+
+        assert_eq!(puzzle.rucksacks[0].duplicate(), ('p', 16));
+        assert_eq!(puzzle.rucksacks[1].duplicate(), ('L', 38));
+        assert_eq!(puzzle.rucksacks[2].duplicate(), ('P', 42));
+        assert_eq!(puzzle.rucksacks[3].duplicate(), ('v', 22));
+        assert_eq!(puzzle.rucksacks[4].duplicate(), ('t', 20));
+        assert_eq!(puzzle.rucksacks[5].duplicate(), ('s', 19));
+    }
+
+    #[test]
+    fn part_1() {
+        let puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+        assert_eq!(puzzle.priorty_sum(), 157);
+    }
+
+    #[test]
+    fn part_2() {
+        let puzzle = super::Puzzle::parse(SAMPLE).unwrap();
+        assert_eq!(puzzle.group_badge_priority_sum(), 70);
+    }
+
+    #[test]
+    fn bad_rucksacks() {
+        let error = super::Puzzle::parse("vJrwpWtwJgWr\nhcsF-MfFFhFp\n").err();
+        assert_eq!(Some(ParseError::new(2, 5, "-", "an item from a to z or A to Z")), error);
+
+        let error = super::Puzzle::parse("vJrwpWtwJgWr\nhcsFMfFFhFp\n").err().map(|e| (e.line, e.column));
+        assert_eq!(Some((2, 1)), error);
+    }
+}
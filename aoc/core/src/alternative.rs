@@ -0,0 +1,103 @@
+//! Other ways of answering the same part, kept to check the day's own answer against.
+//!
+//! A day that keeps its first, naive solution around while it optimises, or tries out a cleverer one before switching,
+//! lists each in [`Solution::alternatives`]. Every fixture and generated input a day is tested with is then answered
+//! every way, and the test fails with just the difference if any of them disagree.
+
+use crate::{Answer, Solution};
+
+/// Another way of answering one part of a day.
+pub struct Alternative<I> {
+    /// What to call it when it disagrees, e.g. "original" or "brute force"
+    pub name: &'static str,
+    pub part: u8,
+    pub solve: fn(&I) -> Answer,
+}
+
+impl<I> Alternative<I> {
+    pub fn new(name: &'static str, part: u8, solve: fn(&I) -> Answer) -> Alternative<I> {
+        Alternative { name, part, solve }
+    }
+}
+
+/// Answer `input` every way the day knows, and panic if any alternative disagrees with the day's own answer. `what`
+/// says which input it was, for the message.
+pub fn check<S: Solution>(input: &S::Input, what: &str) {
+    compare::<S>(input, S::alternatives(), what);
+}
+
+fn compare<S: Solution>(input: &S::Input, alternatives: Vec<Alternative<S::Input>>, what: &str) {
+    for alternative in alternatives {
+        let expected = S::part(input, alternative.part).to_string();
+        let actual = (alternative.solve)(input).to_string();
+
+        if expected != actual {
+            panic!(
+                "The {} way of answering part {} disagrees with the day's answer for {}:\n{}",
+                alternative.name,
+                alternative.part,
+                what,
+                diff(&expected, &actual)
+            );
+        }
+    }
+}
+
+/// As little as it takes to see how two answers differ: both of a one line answer, marking where they part ways, or
+/// only the lines that differ of a picture.
+pub fn diff(expected: &str, actual: &str) -> String {
+    if !expected.contains('\n') && !actual.contains('\n') {
+        let same = expected.chars().zip(actual.chars()).take_while(|(e, a)| e == a).count();
+        return format!("  day's: {}\n  other: {}\n         {}^", expected, actual, " ".repeat(same));
+    }
+
+    let (expected, actual): (Vec<&str>, Vec<&str>) = (expected.lines().collect(), actual.lines().collect());
+    let mut diff = Vec::new();
+
+    for line in 0..expected.len().max(actual.len()) {
+        let (e, a) = (expected.get(line), actual.get(line));
+        if e != a {
+            diff.push(format!("  line {}:", line + 1));
+            diff.push(format!("    day's: {}", e.unwrap_or(&"(nothing)")));
+            diff.push(format!("    other: {}", a.unwrap_or(&"(nothing)")));
+        }
+    }
+
+    diff.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::Sum;
+
+    #[test]
+    fn agreeing() {
+        check::<Sum>(&vec![1, 2, 3], "1,2,3");
+    }
+
+    #[test]
+    #[should_panic(expected = "The biggest way of answering part 1 disagrees with the day's answer for 3,1:\n  day's: 4\n  other: 3\n         ^")]
+    fn disagreeing() {
+        // Wrong as soon as there's more than one number
+        let biggest = Alternative::new("biggest", 1, |input: &Vec<u32>| input.iter().max().copied().unwrap_or(0).into());
+        compare::<Sum>(&vec![3, 1], vec![biggest], "3,1");
+    }
+
+    #[test]
+    fn one_line_diffs() {
+        assert_eq!("  day's: 1588\n  other: 1598\n           ^", diff("1588", "1598"));
+        assert_eq!("  day's: 15\n  other: 150\n           ^", diff("15", "150"));
+    }
+
+    #[test]
+    fn picture_diffs() {
+        let expected = "#####\n#...#\n#...#\n#####";
+        let actual = "#####\n#.#.#\n#...#";
+
+        assert_eq!(
+            "  line 2:\n    day's: #...#\n    other: #.#.#\n  line 4:\n    day's: #####\n    other: (nothing)",
+            diff(expected, actual)
+        );
+    }
+}
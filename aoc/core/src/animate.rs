@@ -0,0 +1,197 @@
+//! Simulations played out in the terminal, each frame drawn over the last.
+//!
+//! Days build their frames from the same text their `Display` impls give, with [`paint`] to pick things out, and hand
+//! them to a [`Screen`]. Frames go to stderr so the answers on stdout can still be read by the runner. When stderr
+//! isn't a terminal there's nothing to redraw in place, so the screen skips the escapes, colours and delays and just
+//! shows the last frame.
+
+use std::io::{self, IsTerminal, Write};
+use std::thread;
+use std::time::Duration;
+
+/// How long each frame stays up unless `--delay` says otherwise
+pub const DEFAULT_DELAY_MS: u64 = 100;
+
+/// Set (to anything) to turn colours off everywhere, as <https://no-color.org> asks
+pub const NO_COLOUR_ENV: &str = "NO_COLOR";
+
+/// The terminal colours frames can use
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Colour {
+    fn code(self) -> u8 {
+        match self {
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+            Colour::White => 97,
+        }
+    }
+}
+
+/// Some text in a colour, for a screen to show or leave plain as it sees fit
+pub fn paint(text: &str, colour: Colour) -> String {
+    format!("\x1b[{}m{}\x1b[0m", colour.code(), text)
+}
+
+/// Take the colours back out
+pub fn strip(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // An escape runs up to and including its first letter
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            plain.push(c);
+        }
+    }
+
+    plain
+}
+
+/// A bar for each labelled count, the biggest `width` blocks long and the rest in proportion
+pub fn bars(rows: &[(String, u128)], width: usize) -> String {
+    let label_width = rows.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+    let biggest = rows.iter().map(|&(_, count)| count).max().unwrap_or(0).max(1);
+
+    rows.iter()
+        .map(|(label, count)| {
+            let length = (count * width as u128).div_ceil(biggest) as usize;
+            format!("{:>w$} {} {}", label, "█".repeat(length), count, w = label_width)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Where frames get drawn
+pub struct Screen<W: Write = io::Stderr> {
+    out: W,
+    /// Redraw each frame over the last and wait between them, rather than only showing the last
+    live: bool,
+    colour: bool,
+    delay: Duration,
+    /// How many lines the frame on screen takes up, to move back over
+    drawn: usize,
+    last: Option<String>,
+}
+
+impl Screen {
+    /// The terminal on stderr, if that's what it is
+    pub fn stderr(delay: Duration, colour: bool) -> Screen {
+        let live = io::stderr().is_terminal();
+        let colour = colour && std::env::var_os(NO_COLOUR_ENV).is_none();
+
+        Screen::new(io::stderr(), live, colour, delay)
+    }
+}
+
+impl<W: Write> Screen<W> {
+    pub fn new(out: W, live: bool, colour: bool, delay: Duration) -> Screen<W> {
+        Screen {
+            out,
+            live,
+            colour: colour && live,
+            delay,
+            drawn: 0,
+            last: None,
+        }
+    }
+
+    /// Draw the next frame. Frames taller than the terminal can't be drawn over, so they scroll instead.
+    pub fn show(&mut self, frame: &str) -> io::Result<()> {
+        // Displays of grids end every row with a newline, the last included
+        let frame = frame.trim_end_matches('\n');
+        let frame = if self.colour { frame.to_string() } else { strip(frame) };
+
+        if !self.live {
+            self.last = Some(frame);
+            return Ok(());
+        }
+
+        if self.drawn > 0 {
+            // Back up to where the last frame started and clear everything after it
+            write!(self.out, "\x1b[{}A\x1b[J", self.drawn)?;
+        }
+        writeln!(self.out, "{}", frame)?;
+        self.out.flush()?;
+        self.drawn = frame.lines().count().max(1);
+
+        thread::sleep(self.delay);
+        Ok(())
+    }
+
+    /// Leave the last frame showing, and give back whatever the frames went to
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(frame) = self.last.take() {
+            writeln!(self.out, "{}", frame)?;
+        }
+        self.out.flush()?;
+
+        Ok(self.out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn animate(live: bool, colour: bool, frames: &[&str]) -> String {
+        let mut screen = Screen::new(Vec::new(), live, colour, Duration::ZERO);
+        for frame in frames {
+            screen.show(frame).unwrap();
+        }
+
+        String::from_utf8(screen.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn colours() {
+        let painted = format!("a{}c", paint("b", Colour::Red));
+
+        assert_eq!("a\x1b[31mb\x1b[0mc", painted);
+        assert_eq!("abc", strip(&painted));
+        assert_eq!("plain", strip("plain"));
+    }
+
+    #[test]
+    fn bar_charts() {
+        let rows = vec![("0".to_string(), 3), ("10".to_string(), 12), ("2".to_string(), 0)];
+
+        assert_eq!(" 0 █ 3\n10 ████ 12\n 2  0", bars(&rows, 4));
+        assert_eq!("", bars(&[], 4));
+    }
+
+    #[test]
+    fn live() {
+        let frames = [paint("1", Colour::Yellow), "2\n2\n".to_string(), "3".to_string()];
+        let frames: Vec<&str> = frames.iter().map(|f| f.as_str()).collect();
+
+        assert_eq!("\x1b[33m1\x1b[0m\n\x1b[1A\x1b[J2\n2\n\x1b[2A\x1b[J3\n", animate(true, true, &frames));
+        assert_eq!("1\n\x1b[1A\x1b[J2\n2\n\x1b[2A\x1b[J3\n", animate(true, false, &frames));
+    }
+
+    #[test]
+    fn not_a_terminal() {
+        // Just the last frame, plainly
+        assert_eq!("3\n", animate(false, true, &["1", "2\n2", &paint("3", Colour::Red)]));
+        assert_eq!("", animate(false, true, &[]));
+    }
+}
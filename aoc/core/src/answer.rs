@@ -0,0 +1,103 @@
+use std::fmt;
+
+use num_bigint::BigInt;
+
+/// Whatever a part came up with. Most days count something, a few overflow 64 bits, and some draw their answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(BigInt),
+    /// Free-form, possibly multi-line, text such as day 13's folded paper
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::BigInteger(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+macro_rules! from_small_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Integer(n.into())
+            }
+        })*
+    };
+}
+
+macro_rules! from_large_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            // Only reach for a big integer when we really have to, so equal answers compare equal
+            fn from(n: $t) -> Self {
+                match i64::try_from(n) {
+                    Ok(n) => Answer::Integer(n),
+                    Err(_) => Answer::BigInteger(BigInt::from(n)),
+                }
+            }
+        })*
+    };
+}
+
+from_small_integer!(u8, u16, u32, i8, i16, i32, i64);
+from_large_integer!(u64, usize, u128, i128, isize);
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        match i64::try_from(&n) {
+            Ok(n) => Answer::Integer(n),
+            Err(_) => Answer::BigInteger(n),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(Answer::Integer(42), Answer::from(42u32));
+        assert_eq!(Answer::Integer(-7), Answer::from(-7i32));
+        assert_eq!(Answer::Integer(26984457539), Answer::from(26984457539usize));
+        assert_eq!("26984457539", Answer::from(26984457539usize).to_string());
+    }
+
+    #[test]
+    fn big_integers() {
+        let huge = u64::MAX as u128 * 3;
+        let answer = Answer::from(huge);
+
+        assert_eq!(Answer::BigInteger(BigInt::from(huge)), answer);
+        assert_eq!("55340232221128654845", answer.to_string());
+
+        // Small enough after all
+        assert_eq!(Answer::Integer(5), Answer::from(BigInt::from(5)));
+    }
+
+    #[test]
+    fn text() {
+        let answer = Answer::from("#####\n#...#\n#####");
+
+        assert_eq!(Answer::Text("#####\n#...#\n#####".to_string()), answer);
+        assert_eq!("#####\n#...#\n#####", answer.to_string());
+    }
+}
@@ -0,0 +1,40 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Run `f` over and over, returning its median time. The median shrugs off the odd slow run from a cold cache or a
+/// busy machine far better than the mean does.
+pub fn time<T>(iterations: u32, mut f: impl FnMut() -> T) -> Duration {
+    let mut samples: Vec<Duration> = (0..iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+
+    samples.sort();
+    samples[samples.len() / 2]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn runs_every_iteration() {
+        let mut calls = 0;
+        time(5, || calls += 1);
+        assert_eq!(5, calls);
+
+        time(0, || calls += 1);
+        assert_eq!(6, calls);
+    }
+
+    #[test]
+    fn median() {
+        let mut sleeps = [0, 30, 0].into_iter();
+        let median = time(3, || std::thread::sleep(Duration::from_millis(sleeps.next().unwrap())));
+
+        assert!(median < Duration::from_millis(30));
+    }
+}
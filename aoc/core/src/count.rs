@@ -0,0 +1,182 @@
+//! Counts for the days that grow exponentially, like lanternfish and polymers.
+//!
+//! Plain `usize` arithmetic wraps silently in a release build, so a simulation run past the puzzle's horizon quietly
+//! gives the wrong answer. A [`Count`] is either a `u64` that reports [`Overflow`] instead, or a [`BigInt`] that never
+//! runs out. Days are generic over which they use, count in 64 bits by default and only fall back on big integers when
+//! they have to (see [`exact_answer`]). From the command line they count however [`Counting`] says instead, for as
+//! many steps as `--steps` asks and only with big integers given `--big`, so overflowing is an error there.
+
+use std::error::Error;
+use std::fmt;
+
+pub use num_bigint::BigInt;
+use tracing::debug;
+
+use crate::Answer;
+
+/// A count got too big for 64 bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a count overflowed 64 bits")
+    }
+}
+
+impl Error for Overflow {}
+
+/// Something to count with.
+pub trait Count: Clone + Ord + fmt::Debug + fmt::Display + Into<Answer> {
+    fn from_u64(n: u64) -> Self;
+
+    fn zero() -> Self {
+        Self::from_u64(0)
+    }
+
+    fn try_add(&self, other: &Self) -> Result<Self, Overflow>;
+
+    /// Taking away more than there is overflows too.
+    fn try_sub(&self, other: &Self) -> Result<Self, Overflow>;
+
+    /// Add `other` to this count in place.
+    fn try_add_assign(&mut self, other: &Self) -> Result<(), Overflow> {
+        *self = self.try_add(other)?;
+        Ok(())
+    }
+}
+
+impl Count for u64 {
+    fn from_u64(n: u64) -> u64 {
+        n
+    }
+
+    fn try_add(&self, other: &u64) -> Result<u64, Overflow> {
+        u64::checked_add(*self, *other).ok_or(Overflow)
+    }
+
+    fn try_sub(&self, other: &u64) -> Result<u64, Overflow> {
+        u64::checked_sub(*self, *other).ok_or(Overflow)
+    }
+}
+
+impl Count for BigInt {
+    fn from_u64(n: u64) -> BigInt {
+        BigInt::from(n)
+    }
+
+    fn try_add(&self, other: &BigInt) -> Result<BigInt, Overflow> {
+        Ok(self + other)
+    }
+
+    fn try_sub(&self, other: &BigInt) -> Result<BigInt, Overflow> {
+        if other > self {
+            return Err(Overflow);
+        }
+        Ok(self - other)
+    }
+}
+
+/// Add up counts, stopping at the first overflow.
+pub fn sum<'a, C: Count + 'a>(counts: impl IntoIterator<Item = &'a C>) -> Result<C, Overflow> {
+    counts.into_iter().try_fold(C::zero(), |total, count| total.try_add(count))
+}
+
+/// How to count a simulation from the command line: for `--steps` steps (days, rounds, ...) rather than the number
+/// the puzzle asks for, and with big integers for `--big`. By default each part counts its own number of steps in 64
+/// bits.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Counting {
+    pub steps: Option<usize>,
+    pub big: bool,
+}
+
+impl Counting {
+    /// How many steps to count for, given the number the puzzle asks for
+    pub fn steps_or(&self, puzzle: usize) -> usize {
+        self.steps.unwrap_or(puzzle)
+    }
+
+    /// Answer with `exact` when counting with big integers, or with `checked` otherwise, which reports an overflow
+    /// rather than trying again.
+    pub fn answer(
+        &self,
+        checked: impl FnOnce() -> Result<u64, Overflow>,
+        exact: impl FnOnce() -> Result<BigInt, Overflow>,
+    ) -> Result<Answer, Overflow> {
+        if self.big {
+            exact().map(Answer::from)
+        } else {
+            checked().map(Answer::from)
+        }
+    }
+}
+
+/// Answer with a count worked out in 64 bits when it fits, or worked out again with big integers when it doesn't, so
+/// the answer is exact either way without every run paying for big integers.
+pub fn exact_answer(
+    checked: impl FnOnce() -> Result<u64, Overflow>,
+    exact: impl FnOnce() -> Result<BigInt, Overflow>,
+) -> Answer {
+    match checked() {
+        Ok(count) => count.into(),
+        Err(Overflow) => {
+            debug!("Overflowed 64 bits, so counting again with big integers");
+            exact().expect("Big integers can only overflow by going negative").into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn checked() {
+        assert_eq!(Ok(5), 2u64.try_add(&3));
+        assert_eq!(Err(Overflow), u64::MAX.try_add(&1));
+        assert_eq!(Err(Overflow), 2u64.try_sub(&3));
+
+        let mut count = u64::MAX - 1;
+        assert_eq!(Ok(()), count.try_add_assign(&1));
+        assert_eq!(Err(Overflow), count.try_add_assign(&1));
+        assert_eq!(u64::MAX, count);
+    }
+
+    #[test]
+    fn big() {
+        let max = BigInt::from(u64::MAX);
+
+        assert_eq!(Ok(BigInt::from(u64::MAX) * 2u8), max.try_add(&max));
+        assert_eq!(Err(Overflow), BigInt::from(2).try_sub(&BigInt::from(3)));
+        assert_eq!(Ok(BigInt::from(u64::MAX) * 3u8), sum(&[max.clone(), max.clone(), max]));
+    }
+
+    #[test]
+    fn sums() {
+        assert_eq!(Ok(6), sum(&[1u64, 2, 3]));
+        assert_eq!(Err(Overflow), sum(&[1, u64::MAX]));
+        assert_eq!(Ok(0), sum::<u64>(&[]));
+    }
+
+    #[test]
+    fn answers() {
+        assert_eq!(Answer::Integer(7), exact_answer(|| Ok(7), || panic!("7 fits in 64 bits")));
+
+        let big = BigInt::from(u64::MAX) * 4u8;
+        assert_eq!(Answer::BigInteger(big.clone()), exact_answer(|| Err(Overflow), || Ok(big)));
+    }
+
+    #[test]
+    fn counting() {
+        let checked = Counting::default();
+        let big = Counting { steps: Some(1000), big: true };
+
+        assert_eq!((80, 1000), (checked.steps_or(80), big.steps_or(80)));
+        assert_eq!(Ok(Answer::Integer(7)), checked.answer(|| Ok(7), || panic!("Not counting with big integers")));
+        assert_eq!(Err(Overflow), checked.answer(|| Err(Overflow), || panic!("Not counting with big integers")));
+
+        let huge = BigInt::from(u64::MAX) * 4u8;
+        assert_eq!(Ok(Answer::BigInteger(huge.clone())), big.answer(|| panic!("Counting with big integers"), || Ok(huge)));
+    }
+}
@@ -0,0 +1,142 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Why a puzzle input couldn't be parsed, and exactly where.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The input file, when we know it. Input piped in on stdin has no name.
+    pub file: Option<String>,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in characters rather than bytes
+    pub column: usize,
+    /// The offending text
+    pub text: String,
+    /// What we were hoping to find instead, e.g. "a number" or "one of L, R"
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: impl Into<String>, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            file: None,
+            line,
+            column,
+            text: text.into(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Complain about `text`, which should be a slice of `input` (as it will be after splitting or trimming it), working
+    /// out its line and column from where it sits.
+    pub fn at(input: &str, text: &str, expected: impl Into<String>) -> ParseError {
+        let (line, column) = locate(input, text);
+        ParseError::new(line, column, text, expected)
+    }
+
+    /// Parsers for a single line or token only know positions within what they were given. Once the error makes it
+    /// back to whoever split `inner` out of `outer`, this moves it to the right place in `outer`.
+    pub fn within(mut self, outer: &str, inner: &str) -> ParseError {
+        let (line, column) = locate(outer, inner);
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line += line - 1;
+        self
+    }
+
+    pub fn in_file(mut self, file: impl Into<String>) -> ParseError {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}", file, self.line, self.column)?,
+            None => write!(f, "line {}, column {}", self.line, self.column)?,
+        }
+
+        write!(f, ": expected {} but found {:?}", self.expected, self.text)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parse a number out of `text`, a slice of `input`, with a proper error if it isn't one.
+pub fn parse_number<T: FromStr>(input: &str, text: &str) -> Result<T, ParseError> {
+    text.parse().map_err(|_| ParseError::at(input, text, "a number"))
+}
+
+/// Where `text` starts within `input`, as a 1-based line and column. Anything that isn't really a slice of `input` gets
+/// searched for instead.
+fn locate(input: &str, text: &str) -> (usize, usize) {
+    let start = input.as_ptr() as usize;
+    let at = text.as_ptr() as usize;
+
+    let offset = if at >= start && at + text.len() <= start + input.len() {
+        at - start
+    } else {
+        input.find(text).unwrap_or(0)
+    };
+
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+
+    (line, column)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "forward 5\ndown 5\nsideways 8\n";
+
+    #[test]
+    fn locates_slices() {
+        let line = INPUT.lines().nth(2).unwrap();
+        let error = ParseError::at(INPUT, line.split(' ').next().unwrap(), "forward, up or down");
+
+        assert_eq!(ParseError::new(3, 1, "sideways", "forward, up or down"), error);
+
+        let error = ParseError::at(INPUT, &line[9..], "a small number");
+        assert_eq!((3, 10), (error.line, error.column));
+    }
+
+    #[test]
+    fn columns_count_characters() {
+        let input = "🎄🎄 x";
+        let error = ParseError::at(input, &input[9..], "a tree");
+
+        assert_eq!((1, 4), (error.line, error.column));
+    }
+
+    #[test]
+    fn within() {
+        let line = INPUT.lines().nth(1).unwrap();
+        let error = ParseError::at(line, &line[5..], "a bigger number").within(INPUT, line);
+
+        assert_eq!((2, 6), (error.line, error.column));
+    }
+
+    #[test]
+    fn display() {
+        let error = ParseError::new(2, 6, "5x", "a number");
+        assert_eq!("line 2, column 6: expected a number but found \"5x\"", error.to_string());
+
+        let error = error.in_file("inputs/2021/02.txt");
+        assert_eq!("inputs/2021/02.txt:2:6: expected a number but found \"5x\"", error.to_string());
+    }
+
+    #[test]
+    fn numbers() {
+        let input = "12,x4";
+
+        assert_eq!(Ok(12), parse_number::<u32>(input, &input[..2]));
+        assert_eq!(Err(ParseError::new(1, 4, "x4", "a number")), parse_number::<u32>(input, &input[3..]));
+    }
+}
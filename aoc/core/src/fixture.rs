@@ -0,0 +1,282 @@
+//! Example inputs with their expected answers, kept as files in each day's `fixtures/` directory.
+//!
+//! A fixture is a small TOML header giving whichever answers are known, a `---` line, then the input exactly as the
+//! puzzle gives it:
+//!
+//! ```text
+//! part_1 = 7
+//! part_2 = 5
+//! ---
+//! 199
+//! 200
+//! ```
+//!
+//! Each day's build script calls [`generate_tests`], and [`fixture_tests!`](crate::fixture_tests) turns every fixture
+//! into its own `#[test]`. A new edge case is just a new file.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::Solution;
+
+const SEPARATOR: &str = "---\n";
+
+/// A parsed fixture
+#[derive(Debug, PartialEq)]
+pub struct Fixture<'a> {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    pub input: &'a str,
+}
+
+impl<'a> Fixture<'a> {
+    pub fn parse(text: &'a str) -> Result<Fixture<'a>, String> {
+        let at = separator(text).ok_or("A fixture needs a --- line between its answers and its input")?;
+        let header: toml::Table = text[..at].parse().map_err(|e| format!("Bad fixture header: {}", e))?;
+
+        let answer = |part: &str| match header.get(part) {
+            None => Ok(None),
+            Some(toml::Value::String(answer)) => Ok(Some(answer.clone())),
+            Some(toml::Value::Integer(answer)) => Ok(Some(answer.to_string())),
+            Some(_) => Err(format!("The fixture's {} should be a string or a number", part)),
+        };
+
+        if let Some(key) = header.keys().find(|k| *k != "part_1" && *k != "part_2") {
+            return Err(format!("Unexpected {} in fixture header; only part_1 and part_2 are allowed", key));
+        }
+
+        Ok(Fixture {
+            part_1: answer("part_1")?,
+            part_2: answer("part_2")?,
+            input: &text[at + SEPARATOR.len()..],
+        })
+    }
+
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            _ => self.part_2.as_deref(),
+        }
+    }
+}
+
+/// Where the `---` line starts, if there is one
+const fn separator(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut at = 0;
+
+    while at + SEPARATOR.len() <= bytes.len() {
+        let line_start = at == 0 || bytes[at - 1] == b'\n';
+        if line_start && bytes[at] == b'-' && bytes[at + 1] == b'-' && bytes[at + 2] == b'-' && bytes[at + 3] == b'\n' {
+            return Some(at);
+        }
+        at += 1;
+    }
+
+    None
+}
+
+/// Just the input part of a fixture. This works in a `const`, for tests that poke at a day's internals with the same
+/// example (see [`fixture!`](crate::fixture)).
+pub const fn input(text: &str) -> &str {
+    let Some(at) = separator(text) else {
+        panic!("A fixture needs a --- line between its answers and its input");
+    };
+
+    let (_, input) = text.as_bytes().split_at(at + SEPARATOR.len());
+    match std::str::from_utf8(input) {
+        Ok(input) => input,
+        Err(_) => panic!("Fixtures must be UTF-8"),
+    }
+}
+
+/// Solve a fixture and check every answer it gives. This is what each generated test calls. The input is solved twice:
+/// once as written, and once as if saved on Windows (BOM, CRLF, a trailing blank line), which must make no difference.
+/// Days that can [stream](crate::stream) are checked a line at a time as well, from the untidied input, and days with
+/// [alternatives](crate::alternative) have each of them checked against the day's own answers.
+pub fn check<S: Solution>(text: &str) {
+    let fixture = Fixture::parse(text).unwrap_or_else(|e| panic!("{}", e));
+
+    assert!(fixture.part_1.is_some() || fixture.part_2.is_some(), "The fixture doesn't give any answers to check");
+
+    let windows = format!("\u{feff}{}\r\n\r\n", fixture.input.replace('\n', "\r\n"));
+    for (saved, raw) in [("as written", fixture.input), ("with Windows line endings", windows.as_str())] {
+        let input = S::parse(&crate::input::normalise(raw)).unwrap_or_else(|e| panic!("Couldn't parse the fixture {}: {}", saved, e));
+
+        for part in [1, 2] {
+            if let Some(expected) = fixture.answer(part) {
+                assert_eq!(expected, S::part(&input, part).to_string(), "Wrong answer for part {} {}", part, saved);
+            }
+        }
+        crate::alternative::check::<S>(&input, &format!("the fixture {}", saved));
+
+        if let Some(streamed) = S::stream(&mut raw.as_bytes()) {
+            let streamed = streamed.unwrap_or_else(|e| panic!("Couldn't stream the fixture {}: {}", saved, e));
+            for part in [1, 2] {
+                if let Some(expected) = fixture.answer(part) {
+                    assert_eq!(expected, streamed[part as usize - 1].to_string(), "Wrong answer for part {} streamed {}", part, saved);
+                }
+            }
+        }
+    }
+}
+
+/// Every fixture file in a crate's `fixtures/` directory, in name order
+pub fn files(crate_dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(crate_dir.join("fixtures"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "txt"))
+        .collect();
+
+    files.sort();
+    files
+}
+
+/// For a day's `build.rs`: write a `#[test]` for each of its fixtures, for [`fixture_tests!`](crate::fixture_tests)
+/// to pull in. Cargo re-runs this whenever anything in `fixtures/` changes, so new files are picked up straight away.
+/// A fixture without any answers yet, like the empty example a new day starts with, is skipped until it has some.
+pub fn generate_tests() {
+    let crate_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").expect("generate_tests is for build scripts"));
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").expect("generate_tests is for build scripts"));
+
+    println!("cargo:rerun-if-changed=fixtures");
+    fs::write(out_dir.join("fixtures.rs"), tests(&files(&crate_dir))).expect("Couldn't write the fixture tests");
+}
+
+fn tests(files: &[PathBuf]) -> String {
+    let mut code = String::new();
+
+    for file in files {
+        if fs::read_to_string(file).is_ok_and(|text| Fixture::parse(&text).is_ok_and(|f| f.part_1.is_none() && f.part_2.is_none())) {
+            continue;
+        }
+
+        let name = test_name(&file.file_stem().unwrap_or_default().to_string_lossy());
+        writeln!(code, "#[test]\nfn {}() {{", name).unwrap();
+        writeln!(code, "    aoc_core::fixture::check::<Day>(include_str!({:?}));", file.display().to_string()).unwrap();
+        writeln!(code, "}}\n").unwrap();
+    }
+
+    code
+}
+
+/// `sonar-depths-test` -> `sonar_depths_test`, `2-rows` -> `fixture_2_rows`
+fn test_name(stem: &str) -> String {
+    let name: String = stem.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' }).collect();
+
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("fixture_{}", name)
+    }
+}
+
+/// One `#[test]` per fixture file for the given [`Solution`], generated by the day's build script, plus one checking its
+/// input generator (see [`generate::check`](crate::generate::check)) and an ignored one stress testing it (see
+/// [`generate::stress`](crate::generate::stress)):
+///
+/// ```ignore
+/// aoc_core::fixture_tests!(Day01);
+/// ```
+#[macro_export]
+macro_rules! fixture_tests {
+    ($day:ident) => {
+        #[cfg(test)]
+        mod fixtures {
+            type Day = super::$day;
+
+            include!(concat!(env!("OUT_DIR"), "/fixtures.rs"));
+
+            #[test]
+            fn generated_inputs() {
+                $crate::generate::check::<Day>();
+            }
+
+            #[test]
+            #[ignore = "slow in a debug build; run with cargo test --release -- --ignored"]
+            fn generated_inputs_at_scale() {
+                $crate::generate::stress::<Day>();
+            }
+        }
+    };
+}
+
+/// The input from one of this crate's fixtures, by name, usable as a `const`:
+///
+/// ```ignore
+/// const SAMPLE: &str = aoc_core::fixture!("sonar-depths-test");
+/// ```
+#[macro_export]
+macro_rules! fixture {
+    ($name:literal) => {
+        $crate::fixture::input(include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/", $name, ".txt")))
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::Sum;
+
+    const SAMPLE: &str = "part_1 = 6\npart_2 = \"1\\n2\\n3\"\n---\n1,2,3";
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Ok(Fixture { part_1: Some("6".to_string()), part_2: Some("1\n2\n3".to_string()), input: "1,2,3" }),
+            Fixture::parse(SAMPLE)
+        );
+
+        let fixture = Fixture::parse("# Only part 1 is in the puzzle text\npart_1 = 6\n---\n\n--\n---\n").unwrap();
+        assert_eq!((Some("6"), None), (fixture.answer(1), fixture.answer(2)));
+        assert_eq!("\n--\n---\n", fixture.input);
+
+        assert!(Fixture::parse("part_1 = 6\n1,2,3").is_err());
+        assert!(Fixture::parse("part_3 = 6\n---\n1,2,3").is_err());
+        assert!(Fixture::parse("part_1 = [6]\n---\n1,2,3").is_err());
+    }
+
+    #[test]
+    fn const_input() {
+        const INPUT: &str = input(SAMPLE);
+        assert_eq!("1,2,3", INPUT);
+    }
+
+    #[test]
+    fn checks_answers() {
+        check::<Sum>(SAMPLE);
+        check::<Sum>("part_2 = \"1\\n2\"\n---\n1,2");
+    }
+
+    #[test]
+    #[should_panic(expected = "Wrong answer for part 1 as written")]
+    fn wrong_answers() {
+        check::<Sum>("part_1 = 7\n---\n1,2,3");
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!("sonar_depths_test", test_name("sonar-depths-test"));
+        assert_eq!("fixture_2_rows", test_name("2-rows"));
+        assert_eq!("example", test_name("Example"));
+    }
+
+    #[test]
+    fn generated_tests() {
+        let code = tests(&[PathBuf::from("/aoc/fixtures/col-winner.txt")]);
+        assert_eq!(
+            "#[test]\nfn col_winner() {\n    aoc_core::fixture::check::<Day>(include_str!(\"/aoc/fixtures/col-winner.txt\"));\n}\n\n",
+            code
+        );
+
+        let dir = std::env::temp_dir().join(format!("aoc-fixtures-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("example.txt"), "# part_1 = 0\n---\n").unwrap();
+        assert_eq!("", tests(&[dir.join("example.txt")]), "A fixture without answers has nothing to test yet");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
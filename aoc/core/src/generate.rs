@@ -0,0 +1,207 @@
+//! Made-up puzzle inputs of any size, for finding out how a day copes with far more than the puzzle ever gave it.
+//!
+//! Each day describes what its size means (lines, boards, the side of a map...) in its
+//! [`Solution::generate`](crate::Solution::generate). The same size and seed always give the same input, so anything a
+//! generated input turns up can be reproduced. Inputs are built so their answers are known without solving them, where
+//! that's possible, and every day's binary checks them when run with `--generate`.
+
+use std::fmt::Write as _;
+use std::ops::RangeInclusive;
+
+use crate::{Answer, Solution};
+
+/// The size every day's generator is tried at in its tests: big enough to get past the edge cases, small enough to
+/// solve in a debug build.
+pub const CHECK_SIZE: usize = 20;
+
+/// The size every day's generator is stress tested at, by an ignored test of its own, for finding what's quadratic, or
+/// recursive, or allocates a grid per point. Run it in a release build: `cargo test --release -- --ignored`.
+pub const STRESS_SIZE: usize = 2000;
+
+/// A small, seeded random number generator (SplitMix64). Nothing here needs to be unpredictable, just repeatable
+/// everywhere without pulling in a dependency.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0` up to, but not including, `n`
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "There's no number below 0 to pick");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// A number from the range, both ends included
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        range.start() + self.below(range.end() - range.start() + 1)
+    }
+
+    /// True one time in `n`
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// A generated input, with whichever answers it was built to have
+#[derive(Clone, Debug, PartialEq)]
+pub struct Generated {
+    pub input: String,
+    pub part_1: Option<Answer>,
+    pub part_2: Option<Answer>,
+}
+
+impl Generated {
+    /// An input without any answers we know of
+    pub fn new(input: String) -> Generated {
+        Generated { input, part_1: None, part_2: None }
+    }
+
+    pub fn answer(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part_1.as_ref(),
+            _ => self.part_2.as_ref(),
+        }
+    }
+
+    /// The input as a [fixture](crate::fixture), ready to drop into a day's `fixtures/` directory
+    pub fn to_fixture(&self) -> String {
+        let mut text = String::new();
+
+        for (key, answer) in [("part_1", &self.part_1), ("part_2", &self.part_2)] {
+            match answer {
+                Some(Answer::Integer(n)) => writeln!(text, "{} = {}", key, n).unwrap(),
+                Some(answer) => writeln!(text, "{} = {}", key, toml::Value::from(answer.to_string())).unwrap(),
+                None => (),
+            }
+        }
+
+        format!("{}---\n{}", text, self.input)
+    }
+}
+
+/// Generate a few small inputs for a day and check they parse and solve to the answers they were built with, streamed
+/// too if the day can, and every alternative way too. This is what [`fixture_tests!`](crate::fixture_tests) adds to every day with a generator.
+pub fn check<S: Solution>() {
+    for seed in 0..3 {
+        check_one::<S>(CHECK_SIZE, seed, true);
+    }
+}
+
+/// Generate one input of [`STRESS_SIZE`] and check it as [`check`] does, except for the alternatives, which are often
+/// the naive versions a day has moved on from and might never finish.
+pub fn stress<S: Solution>() {
+    check_one::<S>(STRESS_SIZE, 0, false);
+}
+
+fn check_one<S: Solution>(size: usize, seed: u64, alternatives: bool) {
+    let Some(generated) = S::generate(size, &mut Rng::new(seed)) else {
+        return;
+    };
+    let what = format!("the input generated with size {} and seed {}", size, seed);
+
+    let input = S::parse(&generated.input).unwrap_or_else(|e| panic!("Couldn't parse {}: {}", what, e));
+    for part in [1, 2] {
+        let answer = S::part(&input, part);
+        if let Some(expected) = generated.answer(part) {
+            assert_eq!(expected.to_string(), answer.to_string(), "Wrong answer for part {} of {}", part, what);
+        }
+    }
+    if alternatives {
+        crate::alternative::check::<S>(&input, &what);
+    }
+
+    if let Some(streamed) = S::stream(&mut generated.input.as_bytes()) {
+        let streamed = streamed.unwrap_or_else(|e| panic!("Couldn't stream {}: {}", what, e));
+        for part in [1, 2] {
+            if let Some(expected) = generated.answer(part) {
+                assert_eq!(expected.to_string(), streamed[part as usize - 1].to_string(), "Wrong answer for part {} of {}, streamed", part, what);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::Sum;
+
+    #[test]
+    fn repeatable() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let mut c = Rng::new(8);
+
+        let a: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+        assert_eq!(a, (0..10).map(|_| b.next_u64()).collect::<Vec<u64>>());
+        assert_ne!(a, (0..10).map(|_| c.next_u64()).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 6];
+
+        for _ in 0..1000 {
+            let n = rng.range(3..=8);
+            assert!((3..=8).contains(&n));
+            seen[n - 3] = true;
+        }
+        assert!(seen.iter().all(|&s| s), "{:?}", seen);
+
+        assert_eq!(0, rng.below(1));
+        assert_eq!(4, rng.range(4..=4));
+    }
+
+    #[test]
+    fn shuffling() {
+        let mut items: Vec<u32> = (0..50).collect();
+        Rng::new(3).shuffle(&mut items);
+
+        assert_ne!((0..50).collect::<Vec<u32>>(), items);
+        items.sort();
+        assert_eq!((0..50).collect::<Vec<u32>>(), items);
+    }
+
+    #[test]
+    fn fixtures() {
+        let generated = Generated {
+            input: "1,2,3".to_string(),
+            part_1: Some(Answer::from(6)),
+            part_2: Some(Answer::from("#.\n.#")),
+        };
+
+        let text = generated.to_fixture();
+        let fixture = crate::fixture::Fixture::parse(&text).unwrap();
+        assert_eq!((Some("6"), Some("#.\n.#"), "1,2,3"), (fixture.answer(1), fixture.answer(2), fixture.input));
+
+        assert_eq!("---\n1,2", Generated::new("1,2".to_string()).to_fixture());
+    }
+
+    #[test]
+    fn checks_generated_answers() {
+        check::<Sum>();
+        stress::<Sum>();
+    }
+}
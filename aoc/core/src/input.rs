@@ -0,0 +1,164 @@
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+use crate::{fixture, Options};
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, PartialEq)]
+pub enum Source {
+    File(PathBuf),
+    /// One of the day's fixtures, whose answers header isn't part of the puzzle
+    Fixture(PathBuf),
+    Stdin,
+    /// Made up by the day itself (see [`crate::generate`])
+    Generated { size: usize, seed: u64 },
+}
+
+/// Work out which input a day should read, in order of preference:
+///
+/// 1. one generated with `--generate`
+/// 2. a path given with `--input`
+/// 3. a fixture named with `--example`, from the day's `fixtures/` directory
+/// 4. whatever is piped in on stdin
+/// 5. the official input, at `inputs/<year>/<day>.txt` in the repository
+pub fn resolve(options: &Options, year: u16, day: u8) -> Result<Source, String> {
+    if let Some(size) = options.generate {
+        return Ok(Source::Generated { size, seed: options.seed });
+    }
+
+    if let Some(path) = &options.input {
+        return Ok(Source::File(path.clone()));
+    }
+
+    let root = || root().ok_or("Can't find the repository (a directory holding inputs/) from here or the binary");
+
+    if let Some(name) = &options.example {
+        return example(&root()?, year, day, name).map(Source::Fixture);
+    }
+
+    if !std::io::stdin().is_terminal() {
+        return Ok(Source::Stdin);
+    }
+
+    let official = official(&root()?, year, day);
+    if !official.is_file() {
+        return Err(format!("No input at {}; pass one with --input, or pipe it in", official.display()));
+    }
+
+    Ok(Source::File(official))
+}
+
+/// Tidy up raw puzzle text before any day sees it, so a file saved on Windows or by an editor with opinions parses just
+/// like the original: a byte order mark is dropped, CRLF becomes LF, trailing whitespace goes from every line, and
+/// blank lines at the end are removed, along with the final newline. Leading whitespace is left alone since some
+/// puzzles (bingo boards, pictures) line things up with it, and leading blank lines are kept so parse errors still
+/// point at the right line.
+pub fn normalise(raw: &str) -> String {
+    let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    let lines: Vec<&str> = raw.lines().map(str::trim_end).collect();
+
+    lines.join("\n").trim_end().to_string()
+}
+
+/// The repository root: the nearest directory with an `inputs/` folder, looking up from the current directory and then
+/// from the binary itself (which lives in `<year>/target/...`), so days run from anywhere.
+pub fn root() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok();
+    let exe = std::env::current_exe().ok();
+
+    [cwd, exe]
+        .into_iter()
+        .flatten()
+        .find_map(|start| start.ancestors().find(|dir| dir.join("inputs").is_dir()).map(Path::to_path_buf))
+}
+
+/// The conventional home of a day's official input, e.g. `inputs/2021/01.txt`
+pub fn official(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join("inputs").join(year.to_string()).join(format!("{:02}.txt", day))
+}
+
+/// Every fixture kept with a day's crate, like `2021/day-01/depthr/fixtures/sonar-depths-test.txt`
+pub fn examples(root: &Path, year: u16, day: u8) -> Vec<PathBuf> {
+    let day_dir = root.join(year.to_string()).join(format!("day-{:02}", day));
+
+    // Older days nest their crate one level down
+    let mut crates = vec![day_dir.clone()];
+    if let Ok(entries) = fs::read_dir(&day_dir) {
+        crates.extend(entries.flatten().map(|e| e.path()).filter(|p| p.join("Cargo.toml").is_file()));
+    }
+
+    crates.iter().flat_map(|dir| fixture::files(dir)).collect()
+}
+
+/// Find an example by its file name, with or without the extension: `sonar-depths-test` or `bingo-test.txt`.
+pub fn example(root: &Path, year: u16, day: u8, name: &str) -> Result<PathBuf, String> {
+    let examples = examples(root, year, day);
+
+    let found = examples.iter().find(|path| {
+        path.file_name().is_some_and(|n| n == name) || path.file_stem().is_some_and(|s| s == name)
+    });
+
+    match found {
+        Some(path) => Ok(path.clone()),
+        None => {
+            let names: Vec<String> = examples.iter().filter_map(|p| p.file_stem()).map(|s| s.to_string_lossy().to_string()).collect();
+            Err(format!("{} day {} has no example called {}; try one of {:?}", year, day, name, names))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn repo_root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+    }
+
+    #[test]
+    fn normalising() {
+        assert_eq!("199\n200", normalise("199\n200"));
+        assert_eq!("199\n200", normalise("199\n200\n"));
+        assert_eq!("199\n200", normalise("\u{feff}199\r\n200\r\n\r\n"));
+        assert_eq!("forward 5\ndown 3", normalise("forward 5  \ndown 3\t\n  \n"));
+        assert_eq!("\n 8  2 23\n\n21  9 14", normalise("\r\n 8  2 23\r\n\r\n21  9 14\r\n"));
+        assert_eq!("", normalise("\u{feff}\r\n"));
+    }
+
+    #[test]
+    fn official_inputs() {
+        assert_eq!(repo_root().join("inputs/2021/01.txt"), official(&repo_root(), 2021, 1));
+        assert!(official(&repo_root(), 2022, 3).is_file());
+    }
+
+    #[test]
+    fn examples_by_name() {
+        let root = repo_root();
+
+        assert_eq!(
+            root.join("2021/day-01/depthr/fixtures/sonar-depths-test.txt"),
+            example(&root, 2021, 1, "sonar-depths-test").unwrap()
+        );
+        assert_eq!(root.join("2021/day-14/fixtures/example.txt"), example(&root, 2021, 14, "example.txt").unwrap());
+
+        let error = example(&root, 2021, 1, "nope").unwrap_err();
+        assert!(error.contains("sonar-depths-test"), "{}", error);
+    }
+
+    #[test]
+    fn examples_are_fixtures() {
+        let examples = examples(&repo_root(), 2022, 1);
+
+        assert!(!examples.is_empty());
+        for path in examples {
+            assert!(path.parent().is_some_and(|p| p.ends_with("fixtures")), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn explicit_paths_win() {
+        let options = Options { input: Some(PathBuf::from("mine.txt")), example: Some("test".to_string()), ..Default::default() };
+        assert_eq!(Ok(Source::File(PathBuf::from("mine.txt"))), resolve(&options, 2021, 1));
+    }
+}
@@ -0,0 +1,22 @@
+//! Shared plumbing for every day of every year.
+
+pub mod alternative;
+pub mod animate;
+mod answer;
+mod bench;
+pub mod count;
+mod error;
+pub mod fixture;
+pub mod generate;
+pub mod input;
+mod logging;
+pub mod parse;
+mod solution;
+pub mod stream;
+#[cfg(test)]
+mod test_support;
+
+pub use answer::Answer;
+pub use error::{parse_number, ParseError};
+pub use logging::LOG_ENV;
+pub use solution::{read_stdin, run, Options, Solution};
@@ -0,0 +1,55 @@
+use std::io::IsTerminal;
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::EnvFilter;
+
+/// Set this to pick exactly which traces to see, using `tracing`'s filter syntax: `debug`, `mappr=trace`,
+/// `diagnosr=debug,warn` and so on. It takes precedence over `-v`.
+pub const LOG_ENV: &str = "AOC_LOG";
+
+/// How much each `-v` turns up the volume. With none at all only warnings get through, so release runs print nothing
+/// but the answers.
+fn level(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+fn filter(verbosity: u8, directives: Option<&str>) -> EnvFilter {
+    EnvFilter::builder()
+        .with_default_directive(level(verbosity).into())
+        .parse_lossy(directives.unwrap_or_default())
+}
+
+/// Send traces to stderr, keeping stdout for the answers the runner reads.
+pub fn init(verbosity: u8) {
+    let directives = std::env::var(LOG_ENV).ok();
+
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter(verbosity, directives.as_deref()))
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .without_time()
+        .try_init();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn verbosity() {
+        assert_eq!(Some(LevelFilter::WARN), filter(0, None).max_level_hint());
+        assert_eq!(Some(LevelFilter::DEBUG), filter(2, None).max_level_hint());
+        assert_eq!(Some(LevelFilter::TRACE), filter(7, None).max_level_hint());
+    }
+
+    #[test]
+    fn directives() {
+        assert_eq!(Some(LevelFilter::TRACE), filter(0, Some("mappr=trace")).max_level_hint());
+        assert_eq!(Some(LevelFilter::ERROR), filter(3, Some("error")).max_level_hint());
+    }
+}
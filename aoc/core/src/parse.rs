@@ -0,0 +1,170 @@
+//! Helpers for the formats puzzle inputs keep coming back to: numbers separated by commas, sections separated by blank
+//! lines, `a -> b` rules and tables of numbers lined up with spaces. Grids of digits or characters are
+//! `aoc_grid::Grid::parse_digits` and friends.
+//!
+//! Every helper takes the text it's to parse and reports errors relative to that text. Helpers that split it up and
+//! hand the pieces on to a closure expect the closure to do the same for its piece, and move any error it returns to
+//! the right place in the whole. A day only needs [`ParseError::within`] for text it split up itself.
+
+use std::str::FromStr;
+
+use crate::{parse_number, ParseError};
+
+/// Parse each piece of `text` between `separator`s with `f`. Pieces are trimmed, so `3, 4` reads the same as `3,4`.
+pub fn separated<T>(
+    text: &str,
+    separator: &str,
+    mut f: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    text.split(separator).map(str::trim).map(|piece| f(piece).map_err(|e| e.within(text, piece))).collect()
+}
+
+/// Numbers separated by `separator`, like `3,4,3,1,2`.
+pub fn numbers<T: FromStr>(text: &str, separator: &str) -> Result<Vec<T>, ParseError> {
+    separated(text, separator, |n| parse_number(n, n))
+}
+
+/// Parse each line of `text` with `f`. Lines are trimmed and blank lines skipped, so indented samples in tests parse
+/// the same as the real input.
+pub fn lines<T>(text: &str, mut f: impl FnMut(&str) -> Result<T, ParseError>) -> Result<Vec<T>, ParseError> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| f(line).map_err(|e| e.within(text, line)))
+        .collect()
+}
+
+/// The runs of lines between blank ones, each as a slice of `text` without the blank lines around it. Any number of
+/// blank lines (or lines of nothing but spaces) between two sections count as one break.
+pub fn sections(text: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in text.split('\n') {
+        if line.trim().is_empty() {
+            if let Some(from) = start.take() {
+                sections.push(&text[from..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.len();
+        }
+        offset += line.len() + 1;
+    }
+    if let Some(from) = start {
+        sections.push(&text[from..end]);
+    }
+
+    sections
+}
+
+/// Exactly two sections, like a polymer template and then its rules. `expected` describes the whole input for the
+/// error when there are more or fewer.
+pub fn two_sections<'a>(text: &'a str, expected: &str) -> Result<(&'a str, &'a str), ParseError> {
+    match sections(text)[..] {
+        [first, second] => Ok((first, second)),
+        [] => Err(ParseError::at(text, text, expected)),
+        [_] => Err(ParseError::at(text, &text[text.len()..], expected)),
+        [_, _, third, ..] => Err(ParseError::at(text, third, expected)),
+    }
+}
+
+/// Split `text` in two at the first `separator`, or complain that it isn't `expected`.
+pub fn split<'a>(text: &'a str, separator: &str, expected: &str) -> Result<(&'a str, &'a str), ParseError> {
+    match text.split_once(separator) {
+        Some((left, right)) => Ok((left.trim(), right.trim())),
+        None => Err(ParseError::at(text, text, expected)),
+    }
+}
+
+/// The two sides of a rule like `CH -> B` or `0,9 -> 5,9`.
+pub fn rule<'a>(text: &'a str, expected: &str) -> Result<(&'a str, &'a str), ParseError> {
+    split(text, " -> ", expected)
+}
+
+/// Rows of numbers lined up with any amount of space between them, like a bingo board. Every row has to be as wide as
+/// the first.
+pub fn table<T: FromStr>(text: &str) -> Result<Vec<Vec<T>>, ParseError> {
+    let mut width = None;
+
+    lines(text, |line| {
+        let row: Vec<&str> = line.split_whitespace().collect();
+        let width = *width.get_or_insert(row.len());
+        if row.len() != width {
+            return Err(ParseError::at(line, line, format!("a row of {} numbers", width)));
+        }
+
+        row.into_iter().map(|n| parse_number(line, n)).collect()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(Ok(vec![3, 4, 3, 1, 2]), super::numbers::<u32>("3,4,3,1,2", ","));
+        assert_eq!(Ok(vec![16, -1]), super::numbers::<i32>("16, -1", ","));
+        assert_eq!(Err(ParseError::new(1, 5, "x", "a number")), super::numbers::<u32>("3,4,x,1", ","));
+        assert_eq!(Err(ParseError::new(1, 3, "", "a number")), super::numbers::<u32>("3,,1", ","));
+    }
+
+    #[test]
+    fn separated() {
+        let checked = super::separated("1,9,2", ",", |n| match parse_number::<u8>(n, n)? {
+            n if n < 9 => Ok(n),
+            _ => Err(ParseError::at(n, n, "a digit below 9")),
+        });
+
+        assert_eq!(Err(ParseError::new(1, 3, "9", "a digit below 9")), checked);
+    }
+
+    #[test]
+    fn lines() {
+        let text = "\n    1\n    22\n\n    x\n";
+
+        assert_eq!(Ok(vec![1, 22]), super::lines("1\n22", |line| parse_number::<u32>(line, line)));
+        assert_eq!(Err(ParseError::new(5, 5, "x", "a number")), super::lines(text, |line| parse_number::<u32>(line, line)));
+    }
+
+    #[test]
+    fn sections() {
+        let text = "a\nb\n\n\nc\n  \nd\ne\n";
+
+        assert_eq!(vec!["a\nb", "c", "d\ne"], super::sections(text));
+        assert_eq!(vec!["x"], super::sections("\n\nx"));
+        assert!(super::sections("").is_empty());
+
+        // Sections are slices of the text, so errors inside them can be moved back to where they really are
+        let third = super::sections(text)[2];
+        let error = ParseError::at(third, &third[2..], "anything but e").within(text, third);
+        assert_eq!(ParseError::new(8, 1, "e", "anything but e"), error);
+    }
+
+    #[test]
+    fn two_sections() {
+        assert_eq!(Ok(("NNCB", "CH -> B\nHH -> N")), super::two_sections("NNCB\n\nCH -> B\nHH -> N", "a template and rules"));
+        assert_eq!(Err(ParseError::new(1, 5, "", "a template and rules")), super::two_sections("NNCB", "a template and rules"));
+        assert_eq!(Err(ParseError::new(5, 1, "x", "a template and rules")), super::two_sections("a\n\nb\n\nx", "a template and rules"));
+    }
+
+    #[test]
+    fn rules() {
+        assert_eq!(Ok(("CH", "B")), rule("CH -> B", "a rule"));
+        assert_eq!(Ok(("0,9", "5,9")), rule("0,9 -> 5,9", "a rule"));
+        assert_eq!(Err(ParseError::new(1, 1, "CH => B", "a rule")), rule("CH => B", "a rule"));
+        assert_eq!(Ok(("fold along y", "7")), split("fold along y=7", "=", "a fold"));
+    }
+
+    #[test]
+    fn table() {
+        let board = "22 13 17\n 8  2 23\n21  9 14";
+
+        assert_eq!(Ok(vec![vec![22, 13, 17], vec![8, 2, 23], vec![21, 9, 14]]), super::table::<u32>(board));
+        assert_eq!(Err(ParseError::new(2, 3, "x", "a number")), super::table::<u32>("1 2\n3 x"));
+        assert_eq!(Err(ParseError::new(2, 1, "3 4 5", "a row of 2 numbers")), super::table::<u32>("1 2\n3 4 5"));
+    }
+}
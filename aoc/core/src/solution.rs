@@ -0,0 +1,479 @@
+use std::io::{BufRead, BufReader, Cursor};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::alternative::Alternative;
+use crate::animate::{self, Screen};
+use crate::count::{Counting, Overflow};
+use crate::fixture::Fixture;
+use crate::generate::{Generated, Rng};
+use crate::input::{self, Source};
+use crate::stream::StreamError;
+use crate::{Answer, ParseError};
+
+/// The shape every day shares: turn the puzzle input into something typed, then answer both parts from it.
+///
+/// Parts only get to look at the parsed input. Days that simulate by mutating their puzzle clone it first, so each
+/// part starts from the same place no matter which order (or how often) they're run.
+pub trait Solution {
+    /// Which puzzle this solves, so its input can be found without being told
+    const YEAR: u16;
+    const DAY: u8;
+
+    /// The puzzle input once parsed
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_1(input: &Self::Input) -> Answer;
+    fn part_2(input: &Self::Input) -> Answer;
+
+    /// Run a single part by number
+    fn part(input: &Self::Input, part: u8) -> Answer {
+        match part {
+            1 => Self::part_1(input),
+            2 => Self::part_2(input),
+            _ => panic!("There are only two parts to a day, not {}", part),
+        }
+    }
+
+    /// Make up an input of the given size, whatever size means for this puzzle, along with any answers it's known to
+    /// have by construction. Days without a generator don't override this.
+    fn generate(_size: usize, _rng: &mut Rng) -> Option<Generated> {
+        None
+    }
+
+    /// Draw the puzzle being solved, as a `.png` of how it ends up or a `.gif` of how it gets there. Days without
+    /// anything worth looking at don't override this.
+    fn render(_input: &Self::Input, _to: &Path) -> Option<std::io::Result<()>> {
+        None
+    }
+
+    /// Play the puzzle out in the terminal, a frame at a time. Days without a simulation worth watching don't override
+    /// this.
+    fn animate(_input: &Self::Input, _screen: &mut Screen) -> Option<std::io::Result<()>> {
+        None
+    }
+
+    /// Answer both parts in a single pass as the input is read (see [`crate::stream`]), without ever holding all of
+    /// it, for `--stream`. Days that need the whole input at once don't override this.
+    fn stream(_reader: &mut dyn BufRead) -> Option<Result<[Answer; 2], StreamError>> {
+        None
+    }
+
+    /// Answer a part by counting up a simulation the way `counting` says (see [`crate::count`]), for `--steps` and
+    /// `--big`: the puzzle's own horizon is only where it asks to stop. A count that overflows 64 bits is an error
+    /// unless counting with big integers. Days that don't count up a simulation don't override this.
+    fn count(_input: &Self::Input, _part: u8, _counting: &Counting) -> Option<Result<Answer, Overflow>> {
+        None
+    }
+
+    /// Other ways of answering either part, such as the first naive attempt kept while optimising, which every
+    /// fixture and generated input is also answered with in tests (see [`crate::alternative`]). Days with only the one
+    /// way don't override this.
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        Vec::new()
+    }
+}
+
+/// Command line options understood by every day's binary.
+#[derive(Debug, Default, PartialEq)]
+pub struct Options {
+    /// Only run this part, rather than both
+    pub part: Option<u8>,
+    /// Read the puzzle from this file rather than stdin
+    pub input: Option<PathBuf>,
+    /// Read one of the day's examples instead, by name
+    pub example: Option<String>,
+    /// How chatty to be, one step per `-v`. See [`crate::LOG_ENV`] for finer control.
+    pub verbosity: u8,
+    /// Time parsing and each part over this many runs, printing `Bench parse: <ns>` and `Bench part N: <ns>`
+    pub bench: Option<u32>,
+    /// Solve a generated input of this size instead, checking whatever answers it was built with
+    pub generate: Option<usize>,
+    /// What to seed the generator with
+    pub seed: u64,
+    /// Print the generated input as a fixture rather than solving it
+    pub emit: bool,
+    /// Draw the puzzle to this file as well as solving it
+    pub render: Option<PathBuf>,
+    /// Play the puzzle out in the terminal before answering
+    pub animate: bool,
+    /// How long to show each frame of the animation for, in milliseconds
+    pub delay: Option<u64>,
+    /// Animate without colours
+    pub no_colour: bool,
+    /// Answer a line at a time as the input is read, rather than reading it all first
+    pub stream: bool,
+    /// Count the day's simulation for this many steps rather than the puzzle's own number
+    pub steps: Option<usize>,
+    /// Count with big integers rather than failing when 64 bits overflow
+    pub big: bool,
+}
+
+impl Options {
+    pub fn parse(args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options::default();
+        let mut args = args;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" => {
+                    let part = args.next().ok_or("--part needs a value")?;
+                    match part.as_str() {
+                        "1" => options.part = Some(1),
+                        "2" => options.part = Some(2),
+                        _ => return Err(format!("--part must be 1 or 2, not {}", part)),
+                    }
+                }
+                "--input" => {
+                    let input = args.next().ok_or("--input needs a path")?;
+                    options.input = Some(PathBuf::from(input));
+                }
+                "--example" => {
+                    let example = args.next().ok_or("--example needs a name, like sonar-depths-test")?;
+                    options.example = Some(example);
+                }
+                "--bench" => {
+                    let iterations = args.next().ok_or("--bench needs a number of iterations")?;
+                    let iterations = iterations.parse().map_err(|_| format!("--bench needs a number of iterations, not {}", iterations))?;
+                    options.bench = Some(iterations);
+                }
+                "--generate" => {
+                    let size = args.next().ok_or("--generate needs a size")?;
+                    let size = size.parse().map_err(|_| format!("--generate needs a size, not {}", size))?;
+                    options.generate = Some(size);
+                }
+                "--seed" => {
+                    let seed = args.next().ok_or("--seed needs a number")?;
+                    options.seed = seed.parse().map_err(|_| format!("--seed needs a number, not {}", seed))?;
+                }
+                "--emit" => options.emit = true,
+                "--render" => {
+                    let render = args.next().ok_or("--render needs a path ending in .png or .gif")?;
+                    options.render = Some(PathBuf::from(render));
+                }
+                "--animate" => options.animate = true,
+                "--delay" => {
+                    let delay = args.next().ok_or("--delay needs a number of milliseconds")?;
+                    let delay = delay.parse().map_err(|_| format!("--delay needs a number of milliseconds, not {}", delay))?;
+                    options.delay = Some(delay);
+                }
+                "--no-colour" => options.no_colour = true,
+                "--stream" => options.stream = true,
+                "--steps" => {
+                    let steps = args.next().ok_or("--steps needs a number of steps")?;
+                    options.steps = Some(steps.parse().map_err(|_| format!("--steps needs a number of steps, not {}", steps))?);
+                }
+                "--big" => options.big = true,
+                "--verbose" => options.verbosity += 1,
+                _ if arg.len() > 1 && arg.strip_prefix('-').is_some_and(|v| v.chars().all(|c| c == 'v')) => {
+                    options.verbosity += arg.len() as u8 - 1;
+                }
+                _ => return Err(format!("Unexpected argument {}", arg)),
+            }
+        }
+
+        if options.emit && options.generate.is_none() {
+            return Err("--emit prints a generated input, so it needs --generate".to_string());
+        }
+        if (options.delay.is_some() || options.no_colour) && !options.animate {
+            return Err("--delay and --no-colour are for animations, so they need --animate".to_string());
+        }
+        if options.stream && (options.emit || options.render.is_some() || options.animate || options.bench.is_some()) {
+            return Err("--stream only answers as it reads, so it can't --emit, --render, --animate or --bench".to_string());
+        }
+        if options.stream && (options.steps.is_some() || options.big) {
+            return Err("--steps and --big count up a simulation from the whole input, so they can't --stream".to_string());
+        }
+
+        Ok(options)
+    }
+
+    fn counting(&self) -> Counting {
+        Counting { steps: self.steps, big: self.big }
+    }
+
+    fn parts(&self) -> Vec<u8> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        }
+    }
+}
+
+pub fn read_stdin() -> Result<String, std::io::Error> {
+    let mut buf = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut buf)?;
+    Ok(buf)
+}
+
+/// Everything a day's `main` needs to do: read the puzzle (see [`input::resolve`] for where from, and
+/// [`input::normalise`] for the tidying it gets) and print `Part 1: …` and `Part 2: …`. Answers that span several lines
+/// start on the line after their label. Input that won't parse is reported with its position rather than as a panic.
+/// Traces go to stderr, and only when asked for with `-v` or `AOC_LOG`. Days that can draw themselves do so with
+/// `--render <path>`, once the answers are out, and days with a simulation can play it out first with `--animate`. Days
+/// that can answer in a single pass do so with `--stream`, reading a line at a time. Days that count up a simulation
+/// count in 64 bits, failing rather than overflowing, unless given `--big`, and for `--steps` steps if given.
+pub fn run<S: Solution>() -> Result<(), std::io::Error> {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    crate::logging::init(options.verbosity);
+
+    let source = match input::resolve(&options, S::YEAR, S::DAY) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("💥 {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let generated = match source {
+        Source::Generated { size, seed } => match S::generate(size, &mut Rng::new(seed)) {
+            Some(generated) => Some(generated),
+            None => {
+                eprintln!("💥 {} day {} has no input generator", S::YEAR, S::DAY);
+                std::process::exit(2);
+            }
+        },
+        _ => None,
+    };
+
+    if options.emit {
+        if let Some(generated) = &generated {
+            println!("{}", generated.to_fixture());
+        }
+        return Ok(());
+    }
+
+    if options.stream {
+        return stream::<S>(&options, &source, generated.as_ref());
+    }
+
+    let raw = match (&source, &generated) {
+        (_, Some(generated)) => generated.input.clone(),
+        (Source::File(path) | Source::Fixture(path), _) => std::fs::read_to_string(path)?,
+        _ => read_stdin()?,
+    };
+
+    let raw = match &source {
+        Source::Fixture(path) => match Fixture::parse(&raw) {
+            Ok(fixture) => fixture.input,
+            Err(e) => {
+                eprintln!("💥 {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        },
+        _ => &raw,
+    };
+
+    let raw = &input::normalise(raw);
+
+    let input = match S::parse(raw) {
+        Ok(input) => input,
+        Err(e) => {
+            let e = match &source {
+                Source::File(path) | Source::Fixture(path) => e.in_file(path.display().to_string()),
+                Source::Stdin | Source::Generated { .. } => e,
+            };
+            eprintln!("💥 {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if options.animate {
+        let delay = Duration::from_millis(options.delay.unwrap_or(animate::DEFAULT_DELAY_MS));
+        let mut screen = Screen::stderr(delay, !options.no_colour);
+        match S::animate(&input, &mut screen) {
+            Some(Ok(())) => {
+                screen.finish()?;
+            }
+            Some(Err(e)) => {
+                eprintln!("💥 Couldn't animate: {}", e);
+                std::process::exit(1);
+            }
+            None => {
+                eprintln!("💥 {} day {} has nothing to animate", S::YEAR, S::DAY);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let counting = options.counting();
+    let mut wrong = Vec::new();
+    for part in options.parts() {
+        let answer = match S::count(&input, part, &counting) {
+            Some(Ok(answer)) => answer,
+            Some(Err(e)) => {
+                eprintln!("💥 Couldn't answer part {}: {}; --big counts with big integers instead", part, e);
+                std::process::exit(1);
+            }
+            None if counting != Counting::default() => {
+                eprintln!("💥 {} day {} has no simulation to count for --steps or --big", S::YEAR, S::DAY);
+                std::process::exit(2);
+            }
+            None => S::part(&input, part),
+        };
+        wrong.extend(show(part, &answer, generated.as_ref()));
+    }
+
+    if let Some(path) = &options.render {
+        match S::render(&input, path) {
+            Some(Ok(())) => eprintln!("🖼  Drew {}", path.display()),
+            Some(Err(e)) => {
+                eprintln!("💥 Couldn't draw {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+            None => {
+                eprintln!("💥 {} day {} has nothing to draw", S::YEAR, S::DAY);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    if let Some(iterations) = options.bench {
+        println!("Bench parse: {}", crate::bench::time(iterations, || S::parse(raw)).as_nanos());
+        for part in options.parts() {
+            println!("Bench part {}: {}", part, crate::bench::time(iterations, || S::part(&input, part)).as_nanos());
+        }
+    }
+
+    if !wrong.is_empty() {
+        eprintln!("{}", wrong.join("\n"));
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Print a part's answer, and say what it should have been if it was generated to be something else.
+fn show(part: u8, answer: &Answer, generated: Option<&Generated>) -> Option<String> {
+    let shown = answer.to_string();
+    if shown.contains('\n') {
+        println!("Part {}:\n{}", part, shown);
+    } else {
+        println!("Part {}: {}", part, shown);
+    }
+
+    generated
+        .and_then(|g| g.answer(part))
+        .filter(|&expected| expected != answer)
+        .map(|expected| format!("❌ Part {} should have been {}", part, expected))
+}
+
+/// `run`, for `--stream`: the input goes straight from wherever it is to the day a line at a time. Only fixtures are
+/// read whole first, to get past their answers; they're small anyway.
+fn stream<S: Solution>(options: &Options, source: &Source, generated: Option<&Generated>) -> Result<(), std::io::Error> {
+    let mut reader: Box<dyn BufRead> = match (source, generated) {
+        (_, Some(generated)) => Box::new(Cursor::new(generated.input.clone())),
+        (Source::File(path), _) => Box::new(BufReader::new(std::fs::File::open(path)?)),
+        (Source::Fixture(path), _) => {
+            let raw = std::fs::read_to_string(path)?;
+            match Fixture::parse(&raw) {
+                Ok(fixture) => Box::new(Cursor::new(fixture.input.to_string())),
+                Err(e) => {
+                    eprintln!("💥 {}: {}", path.display(), e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        _ => Box::new(std::io::stdin().lock()),
+    };
+
+    let answers = match S::stream(&mut reader) {
+        Some(Ok(answers)) => answers,
+        Some(Err(StreamError::Io(e))) => return Err(e),
+        Some(Err(e)) => {
+            let e = match source {
+                Source::File(path) | Source::Fixture(path) => e.in_file(path.display().to_string()),
+                Source::Stdin | Source::Generated { .. } => e,
+            };
+            eprintln!("💥 {}", e);
+            std::process::exit(1);
+        }
+        None => {
+            eprintln!("💥 {} day {} can't answer a line at a time", S::YEAR, S::DAY);
+            std::process::exit(2);
+        }
+    };
+
+    let wrong: Vec<String> = options.parts().into_iter().filter_map(|part| show(part, &answers[part as usize - 1], generated)).collect();
+    if !wrong.is_empty() {
+        eprintln!("{}", wrong.join("\n"));
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::Sum;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter().map(|a| a.to_string()).collect::<Vec<String>>().into_iter()
+    }
+
+    #[test]
+    fn parts_by_number() {
+        let input = Sum::parse("1,2,3").unwrap();
+
+        assert_eq!(Answer::Integer(6), Sum::part(&input, 1));
+        assert_eq!(Answer::Text("1\n2\n3".to_string()), Sum::part(&input, 2));
+    }
+
+    #[test]
+    fn options() {
+        assert_eq!(Ok(Options::default()), Options::parse(args(&[])));
+        assert_eq!(Ok(Options { part: Some(2), ..Default::default() }), Options::parse(args(&["--part", "2"])));
+        assert_eq!(
+            Ok(Options { part: Some(1), input: Some(PathBuf::from("day-01.txt")), ..Default::default() }),
+            Options::parse(args(&["--input", "day-01.txt", "--part", "1"]))
+        );
+        assert_eq!(Ok(Options { verbosity: 3, ..Default::default() }), Options::parse(args(&["-vv", "--verbose"])));
+        assert_eq!(Ok(Options { bench: Some(20), ..Default::default() }), Options::parse(args(&["--bench", "20"])));
+        assert!(Options::parse(args(&["--part", "3"])).is_err());
+        assert!(Options::parse(args(&["--part"])).is_err());
+        assert!(Options::parse(args(&["--bogus"])).is_err());
+        assert!(Options::parse(args(&["-"])).is_err());
+        assert!(Options::parse(args(&["--bench", "lots"])).is_err());
+        assert_eq!(
+            Ok(Options { example: Some("bingo-test".to_string()), ..Default::default() }),
+            Options::parse(args(&["--example", "bingo-test"]))
+        );
+        assert!(Options::parse(args(&["--example"])).is_err());
+        assert_eq!(
+            Ok(Options { generate: Some(5000), seed: 7, emit: true, ..Default::default() }),
+            Options::parse(args(&["--generate", "5000", "--seed", "7", "--emit"]))
+        );
+        assert!(Options::parse(args(&["--emit"])).is_err());
+        assert!(Options::parse(args(&["--generate", "big"])).is_err());
+        assert_eq!(
+            Ok(Options { render: Some(PathBuf::from("day-11.gif")), ..Default::default() }),
+            Options::parse(args(&["--render", "day-11.gif"]))
+        );
+        assert!(Options::parse(args(&["--render"])).is_err());
+        assert_eq!(
+            Ok(Options { animate: true, delay: Some(20), no_colour: true, ..Default::default() }),
+            Options::parse(args(&["--animate", "--delay", "20", "--no-colour"]))
+        );
+        assert!(Options::parse(args(&["--delay", "20"])).is_err());
+        assert!(Options::parse(args(&["--animate", "--delay", "soon"])).is_err());
+        assert_eq!(Ok(Options { stream: true, part: Some(1), ..Default::default() }), Options::parse(args(&["--stream", "--part", "1"])));
+        assert!(Options::parse(args(&["--stream", "--bench", "5"])).is_err());
+        assert_eq!(Ok(Options { steps: Some(1000), big: true, ..Default::default() }), Options::parse(args(&["--steps", "1000", "--big"])));
+        assert!(Options::parse(args(&["--steps", "forever"])).is_err());
+        assert!(Options::parse(args(&["--stream", "--big"])).is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Err(ParseError::new(1, 5, "x", "a number")), Sum::parse("1,2,x"));
+    }
+}
@@ -0,0 +1,137 @@
+//! Reading a puzzle a line at a time, for days that can answer in a single pass over their input.
+//!
+//! A day that overrides [`Solution::stream`](crate::Solution::stream) only ever holds the line it's on and whatever it
+//! keeps between lines, so `--stream` can pipe generated inputs far bigger than memory through it. Lines get the same
+//! tidying [`input::normalise`](crate::input::normalise) gives a whole input, and errors the day reports about a line
+//! are moved to that line of the input.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::ParseError;
+
+/// Why a streamed input couldn't be answered: either it couldn't be read, or it didn't parse.
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl StreamError {
+    pub fn in_file(self, file: impl Into<String>) -> StreamError {
+        match self {
+            StreamError::Parse(e) => StreamError::Parse(e.in_file(file)),
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "Couldn't read the input: {}", e),
+            StreamError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> StreamError {
+        StreamError::Io(e)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(e: ParseError) -> StreamError {
+        StreamError::Parse(e)
+    }
+}
+
+/// Parse each line of `reader` with `f`, blank lines included, as blank lines mean something to some puzzles. Only one
+/// line is held at a time. A byte order mark is dropped and trailing whitespace (`\r` included) trimmed, but leading
+/// whitespace is left alone, just as for a whole input.
+pub fn lines_with_blanks(
+    mut reader: impl BufRead,
+    mut f: impl FnMut(&str) -> Result<(), ParseError>,
+) -> Result<(), StreamError> {
+    let mut buf = String::new();
+    let mut number = 0;
+
+    loop {
+        buf.clear();
+        if reader.read_line(&mut buf)? == 0 {
+            return Ok(());
+        }
+        number += 1;
+
+        let line = match number {
+            1 => buf.strip_prefix('\u{feff}').unwrap_or(&buf),
+            _ => &buf,
+        };
+
+        f(line.trim_end()).map_err(|mut e| {
+            e.line += number - 1;
+            e
+        })?;
+    }
+}
+
+/// Parse each line of `reader` with `f`, trimmed and skipping blank lines like [`parse::lines`](crate::parse::lines).
+pub fn lines(reader: impl BufRead, mut f: impl FnMut(&str) -> Result<(), ParseError>) -> Result<(), StreamError> {
+    lines_with_blanks(reader, |line| match line.trim_start() {
+        "" => Ok(()),
+        trimmed => f(trimmed).map_err(|e| e.within(line, trimmed)),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_number;
+
+    fn collect(input: &str) -> Result<Vec<u32>, StreamError> {
+        let mut numbers = Vec::new();
+        lines(input.as_bytes(), |line| {
+            numbers.push(parse_number(line, line)?);
+            Ok(())
+        })?;
+
+        Ok(numbers)
+    }
+
+    #[test]
+    fn tidies_lines() {
+        assert_eq!(vec![199, 200, 208], collect("\u{feff}199\r\n  200\r\n\r\n208\r\n\r\n").unwrap());
+        assert!(collect("").unwrap().is_empty());
+
+        let mut seen = Vec::new();
+        lines_with_blanks(" 1\n\n2 \r\n".as_bytes(), |line| {
+            seen.push(line.to_string());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(vec![" 1", "", "2"], seen);
+    }
+
+    #[test]
+    fn errors_are_on_their_line() {
+        match collect("1\n\n  3x\n4") {
+            Err(StreamError::Parse(e)) => assert_eq!(ParseError::new(3, 3, "3x", "a number"), e),
+            other => panic!("Expected a parse error, not {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unreadable() {
+        let mut numbers = 0;
+        let error = lines(&b"1\n\xff\n"[..], |_| {
+            numbers += 1;
+            Ok(())
+        });
+        assert!(matches!(error, Err(StreamError::Io(_))));
+        assert_eq!(1, numbers);
+    }
+}
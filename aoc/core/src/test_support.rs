@@ -0,0 +1,43 @@
+//! A day small enough to test the plumbing with.
+
+use crate::alternative::Alternative;
+use crate::generate::{Generated, Rng};
+use crate::{Answer, ParseError, Solution};
+
+/// Comma separated numbers: part 1 is their sum, and part 2 draws them one per line, to stand in for the days whose
+/// answers are pictures.
+pub struct Sum;
+
+impl Solution for Sum {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 1;
+
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        input.split(',').map(|n| crate::parse_number(input, n)).collect()
+    }
+
+    fn part_1(input: &Vec<u32>) -> Answer {
+        input.iter().sum::<u32>().into()
+    }
+
+    fn part_2(input: &Vec<u32>) -> Answer {
+        input.iter().map(|n| n.to_string()).collect::<Vec<String>>().join("\n").into()
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Option<Generated> {
+        let numbers: Vec<usize> = (0..size).map(|_| rng.below(100)).collect();
+        let input = numbers.iter().map(|n| n.to_string()).collect::<Vec<String>>().join(",");
+
+        Some(Generated {
+            input,
+            part_1: Some(numbers.iter().sum::<usize>().into()),
+            part_2: None,
+        })
+    }
+
+    fn alternatives() -> Vec<Alternative<Vec<u32>>> {
+        vec![Alternative::new("doubled and halved", 1, |input| (input.iter().map(|n| n * 2).sum::<u32>() / 2).into())]
+    }
+}
@@ -0,0 +1,91 @@
+use crate::Point;
+
+/// The smallest rectangle holding a set of points, edges included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    /// A box around a single point
+    pub fn at(point: Point) -> Bounds {
+        Bounds { min: point, max: point }
+    }
+
+    /// The box around every point, or nothing if there aren't any.
+    pub fn of(points: impl IntoIterator<Item = Point>) -> Option<Bounds> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Bounds::at(first), Bounds::including))
+    }
+
+    /// Grow the box just enough to take in `point` as well.
+    pub fn including(self, point: Point) -> Bounds {
+        Bounds {
+            min: Point::new(self.min.x.min(point.x), self.min.y.min(point.y)),
+            max: Point::new(self.max.x.max(point.x), self.max.y.max(point.y)),
+        }
+    }
+
+    /// How many columns the box covers
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    /// How many rows the box covers
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// Where `point` falls in a grid laid over the box, counting from its top left corner. Points outside have no
+    /// place in it.
+    pub fn index(&self, point: Point) -> Option<(usize, usize)> {
+        if self.contains(point) {
+            let offset = point - self.min;
+            Some((offset.x as usize, offset.y as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Every point in the box, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let Bounds { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn of() {
+        let bounds = Bounds::of([Point::new(3, -1), Point::new(-2, 4), Point::new(0, 0)]).unwrap();
+
+        assert_eq!(Point::new(-2, -1), bounds.min);
+        assert_eq!(Point::new(3, 4), bounds.max);
+        assert_eq!(6, bounds.width());
+        assert_eq!(6, bounds.height());
+        assert_eq!(36, bounds.points().count());
+        assert_eq!(None, Bounds::of(std::iter::empty()));
+    }
+
+    #[test]
+    fn index() {
+        let bounds = Bounds::of([Point::new(-2, -1), Point::new(1, 1)]).unwrap();
+
+        assert_eq!(Some((0, 0)), bounds.index(Point::new(-2, -1)));
+        assert_eq!(Some((3, 2)), bounds.index(Point::new(1, 1)));
+        assert_eq!(None, bounds.index(Point::new(2, 0)));
+        assert!(bounds.contains(Point::ORIGIN));
+        assert!(!bounds.contains(Point::new(0, -2)));
+        assert_eq!(Some(Point::new(-1, -1)), bounds.points().nth(1));
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use crate::Vector;
+
+/// One of the eight ways out of a square, named for the compass with north at the top of the map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// Up, right, down and left
+    pub const CARDINAL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    pub const DIAGONAL: [Direction; 4] =
+        [Direction::NorthEast, Direction::SouthEast, Direction::SouthWest, Direction::NorthWest];
+
+    /// Clockwise from straight up, diagonals included
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// A single step this way. North is up the map, towards smaller `y`.
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction::North => Vector::new(0, -1),
+            Direction::NorthEast => Vector::new(1, -1),
+            Direction::East => Vector::new(1, 0),
+            Direction::SouthEast => Vector::new(1, 1),
+            Direction::South => Vector::new(0, 1),
+            Direction::SouthWest => Vector::new(-1, 1),
+            Direction::West => Vector::new(-1, 0),
+            Direction::NorthWest => Vector::new(-1, -1),
+        }
+    }
+
+    /// The direction a single step goes in, if it's a step to a neighbouring square at all
+    pub fn from_vector(v: Vector) -> Option<Direction> {
+        Direction::ALL.into_iter().find(|direction| direction.vector() == v)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        Direction::DIAGONAL.contains(&self)
+    }
+
+    /// Turn clockwise by `eighths` of a full turn, or anticlockwise if it's negative.
+    pub fn rotate(self, eighths: i32) -> Direction {
+        Direction::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    /// A quarter turn clockwise
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// A quarter turn anticlockwise
+    pub fn turn_left(self) -> Direction {
+        self.rotate(-2)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotate(4)
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Direction::North => "N",
+            Direction::NorthEast => "NE",
+            Direction::East => "E",
+            Direction::SouthEast => "SE",
+            Direction::South => "S",
+            Direction::SouthWest => "SW",
+            Direction::West => "W",
+            Direction::NorthWest => "NW",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rotation() {
+        assert_eq!(Direction::East, Direction::North.turn_right());
+        assert_eq!(Direction::West, Direction::North.turn_left());
+        assert_eq!(Direction::NorthEast, Direction::NorthWest.rotate(2));
+        assert_eq!(Direction::SouthWest, Direction::North.rotate(-3));
+        assert_eq!(Direction::South, Direction::North.reverse());
+        assert_eq!(Direction::North, Direction::North.rotate(16));
+
+        // Turning a direction turns its step the same way
+        for direction in Direction::ALL {
+            assert_eq!(direction.vector().turn_right(), direction.turn_right().vector());
+            assert_eq!(-direction.vector(), direction.reverse().vector());
+        }
+    }
+
+    #[test]
+    fn vectors() {
+        assert!(Direction::ALL.iter().all(|d| Direction::from_vector(d.vector()) == Some(*d)));
+        assert_eq!(None, Direction::from_vector(Vector::new(2, 0)));
+        assert_eq!(None, Direction::from_vector(Vector::ZERO));
+
+        assert_eq!(4, Direction::ALL.iter().filter(|d| d.is_diagonal()).count());
+        assert!(Direction::CARDINAL.iter().all(|d| d.vector().manhattan() == 1));
+        assert!(Direction::DIAGONAL.iter().all(|d| d.vector().manhattan() == 2));
+    }
+}
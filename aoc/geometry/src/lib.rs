@@ -0,0 +1,292 @@
+//! Points, vectors and directions on a 2D plane, for the days that move things about on one.
+//!
+//! Coordinates are signed, so nothing has to be shifted to stay clear of zero. They follow the same convention as
+//! `aoc_grid`: `x` grows to the right and `y` grows *down*, the way puzzle maps are printed.
+//! North is towards smaller `y`.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use aoc_core::ParseError;
+
+mod bounds;
+mod direction;
+mod segment;
+
+pub use bounds::Bounds;
+pub use direction::Direction;
+pub use segment::Segment;
+
+/// A position on the plane
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The step from one [`Point`] to another
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    /// Parse a point written `x,y`, like `0,9` or `-3,4`.
+    pub fn parse(text: &str) -> Result<Point, ParseError> {
+        match text.split_once(',') {
+            Some((x, y)) if !y.contains(',') => {
+                Ok(Point::new(aoc_core::parse_number(text, x)?, aoc_core::parse_number(text, y)?))
+            }
+            _ => Err(ParseError::at(text, text, "a point like 3,4")),
+        }
+    }
+
+    /// How many steps apart two points are, moving only up, down, left and right
+    pub fn manhattan(self, other: Point) -> i64 {
+        (other - self).manhattan()
+    }
+
+    /// How many steps apart two points are when diagonal steps are allowed too, like a king on a chess board
+    pub fn chebyshev(self, other: Point) -> i64 {
+        (other - self).chebyshev()
+    }
+
+    /// The point one step away in `direction`
+    pub fn step(self, direction: Direction) -> Point {
+        self + direction.vector()
+    }
+
+    /// The points directly above, right, below and left of this one
+    pub fn neighbours_4(self) -> impl Iterator<Item = Point> {
+        Direction::CARDINAL.into_iter().map(move |direction| self.step(direction))
+    }
+
+    /// The eight points around this one, clockwise from straight up
+    pub fn neighbours_8(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |direction| self.step(direction))
+    }
+
+    /// Where this point lands on a grid, if it's not off the top or left edge. A grid still needs to check it's not
+    /// off the other two.
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Vector {
+        Vector { x, y }
+    }
+
+    pub fn manhattan(self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev(self) -> i64 {
+        self.x.abs().max(self.y.abs())
+    }
+
+    /// Each part squashed down to -1, 0 or 1: a single step the same way, as long as it's straight or a true diagonal.
+    pub fn signum(self) -> Vector {
+        Vector::new(self.x.signum(), self.y.signum())
+    }
+
+    /// A quarter turn clockwise (as the map is drawn, with `y` going down)
+    pub fn turn_right(self) -> Vector {
+        Vector::new(-self.y, self.x)
+    }
+
+    /// A quarter turn anticlockwise
+    pub fn turn_left(self) -> Vector {
+        Vector::new(self.y, -self.x)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Point {
+        Point::new(x, y)
+    }
+}
+
+/// A grid position, which always fits
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Point {
+        Point::new(x as i64, y as i64)
+    }
+}
+
+impl From<(i64, i64)> for Vector {
+    fn from((x, y): (i64, i64)) -> Vector {
+        Vector::new(x, y)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, v: Vector) -> Point {
+        Point::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, v: Vector) {
+        *self = *self + v;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, v: Vector) -> Point {
+        Point::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, v: Vector) {
+        *self = *self - v;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, other: Vector) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Vector {
+    fn sub_assign(&mut self, other: Vector) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, n: i64) -> Vector {
+        Vector::new(self.x * n, self.y * n)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl fmt::Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 4);
+
+        assert_eq!(Vector::new(-4, 6), b - a);
+        assert_eq!(b, a + (b - a));
+        assert_eq!(Point::new(-5, 10), a + (b - a) * 2);
+        assert_eq!(Vector::new(4, -6), -(b - a));
+        assert_eq!(Vector::new(-1, 1), (b - a).signum());
+
+        let mut c = a;
+        c += Vector::new(1, 1);
+        c -= Vector::new(0, 3);
+        assert_eq!(Point::new(4, -4), c);
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 4);
+
+        assert_eq!(10, a.manhattan(b));
+        assert_eq!(6, a.chebyshev(b));
+        assert_eq!(0, a.manhattan(a));
+    }
+
+    #[test]
+    fn turns() {
+        let up = Vector::new(0, -1);
+
+        assert_eq!(Vector::new(1, 0), up.turn_right());
+        assert_eq!(Vector::new(-1, 0), up.turn_left());
+        assert_eq!(up, up.turn_right().turn_right().turn_right().turn_right());
+        assert_eq!(Vector::new(-2, -3), Vector::new(3, -2).turn_left());
+    }
+
+    #[test]
+    fn neighbours() {
+        let p = Point::new(0, 0);
+
+        assert_eq!(
+            vec![Point::new(0, -1), Point::new(1, 0), Point::new(0, 1), Point::new(-1, 0)],
+            p.neighbours_4().collect::<Vec<_>>()
+        );
+        assert_eq!(8, p.neighbours_8().count());
+        assert!(p.neighbours_8().all(|n| p.chebyshev(n) == 1));
+    }
+
+    #[test]
+    fn to_index() {
+        assert_eq!(Some((3, 4)), Point::new(3, 4).to_index());
+        assert_eq!(None, Point::new(-1, 4).to_index());
+        assert_eq!(Point::new(3, 4), Point::from((3usize, 4usize)));
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(Ok(Point::new(-3, 4)), Point::parse("-3,4"));
+        assert_eq!(Err(ParseError::new(1, 3, "x", "a number")), Point::parse("3,x"));
+        assert_eq!(Err(ParseError::new(1, 1, "3,4,5", "a point like 3,4")), Point::parse("3,4,5"));
+        assert_eq!(Err(ParseError::new(1, 1, "3", "a point like 3,4")), Point::parse("3"));
+    }
+}
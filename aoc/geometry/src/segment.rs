@@ -0,0 +1,101 @@
+use std::fmt;
+
+use crate::{Point, Vector};
+
+/// A straight line from one point to another, both ends included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    pub fn new(start: Point, end: Point) -> Segment {
+        Segment { start, end }
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    /// Exactly 45 degrees, not just any slope
+    pub fn is_diagonal(&self) -> bool {
+        let v = self.end - self.start;
+        v.x != 0 && v.x.abs() == v.y.abs()
+    }
+
+    /// The smallest whole step that walks from the start to the end, landing on every whole point along the way
+    pub fn step(&self) -> Vector {
+        let v = self.end - self.start;
+        let n = gcd(v.x.abs(), v.y.abs()).max(1);
+
+        Vector::new(v.x / n, v.y / n)
+    }
+
+    /// Every whole point on the line, from the start to the end. That's every square for lines across, down or at 45
+    /// degrees; a steeper or shallower line only passes exactly through some of them.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let step = self.step();
+        let count = if step == Vector::ZERO { 0 } else { (self.end - self.start).chebyshev() / step.chebyshev() };
+        let start = self.start;
+
+        (0..=count).map(move |i| start + step * i)
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} -> {}", self.start, self.end)
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn points(start: (i64, i64), end: (i64, i64)) -> Vec<(i64, i64)> {
+        Segment::new(start.into(), end.into()).points().map(|p| (p.x, p.y)).collect()
+    }
+
+    #[test]
+    fn straight_and_diagonal() {
+        assert_eq!(vec![(1, 1), (1, 2), (1, 3)], points((1, 1), (1, 3)));
+        assert_eq!(vec![(9, 7), (8, 7), (7, 7)], points((9, 7), (7, 7)));
+        assert_eq!(vec![(1, 1), (2, 2), (3, 3)], points((1, 1), (3, 3)));
+        assert_eq!(vec![(9, 7), (8, 8), (7, 9)], points((9, 7), (7, 9)));
+        assert_eq!(vec![(0, 0), (-1, -1)], points((0, 0), (-1, -1)));
+        assert_eq!(vec![(4, 4)], points((4, 4), (4, 4)));
+    }
+
+    #[test]
+    fn other_slopes() {
+        assert_eq!(vec![(0, 0), (2, 1), (4, 2)], points((0, 0), (4, 2)));
+        assert_eq!(vec![(0, 0), (3, -2)], points((0, 0), (3, -2)));
+    }
+
+    #[test]
+    fn kinds() {
+        let across = Segment::new(Point::new(0, 9), Point::new(5, 9));
+        let down = Segment::new(Point::new(2, 2), Point::new(2, 1));
+        let diagonal = Segment::new(Point::new(8, 0), Point::new(0, 8));
+        let steep = Segment::new(Point::new(0, 0), Point::new(1, 3));
+
+        assert!(across.is_horizontal() && !across.is_vertical() && !across.is_diagonal());
+        assert!(down.is_vertical() && !down.is_horizontal() && !down.is_diagonal());
+        assert!(diagonal.is_diagonal() && !diagonal.is_horizontal() && !diagonal.is_vertical());
+        assert!(!steep.is_diagonal() && !steep.is_horizontal() && !steep.is_vertical());
+        assert_eq!("(0, 9) -> (5, 9)", across.to_string());
+    }
+}
@@ -0,0 +1,398 @@
+//! A flat, row-major 2D grid for all the days that hand us a map.
+//!
+//! Positions are `(x, y)` pairs: `x` counts columns from the left and `y` counts rows from the top, so `grid[(x, y)]`
+//! reads the same way round no matter which day is asking. A grid can also be indexed by an `aoc_geometry::Point`, for
+//! days that walk about with signed coordinates; points off the grid simply aren't on it.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use aoc_core::ParseError;
+use aoc_geometry::{Direction, Point};
+
+pub mod render;
+mod view;
+
+pub use view::View;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Wrap up cells that are already laid out row by row.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(width * height, cells.len(), "A {}x{} grid needs {} cells, not {}", width, height, width * height, cells.len());
+
+        Grid { width, height, cells }
+    }
+
+    /// Build a grid from nested rows, the way most days used to hold their maps. Every row must be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            assert_eq!(width, row.len(), "Row {} has {} cells but the first row had {}", y, row.len(), width);
+            cells.extend(row);
+        }
+
+        Grid { width, height, cells }
+    }
+
+    /// Build a grid by asking what goes at each position.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut((usize, usize)) -> T) -> Grid<T> {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(&mut f).collect();
+
+        Grid { width, height, cells }
+    }
+
+    /// Parse a block of text with one cell per character, where `f` turns down any character that isn't `expected`.
+    /// Lines are trimmed and blank lines skipped, so indented samples in tests parse the same as the real input.
+    pub fn parse(input: &str, expected: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for line in input.lines().map(|line| line.trim()).filter(|line| !line.is_empty()) {
+            for (i, c) in line.char_indices() {
+                match f(c) {
+                    Some(cell) => cells.push(cell),
+                    None => return Err(ParseError::at(input, &line[i..i + c.len_utf8()], expected)),
+                }
+            }
+
+            let length = line.chars().count();
+            match width {
+                None => width = Some(length),
+                Some(width) if width != length => {
+                    return Err(ParseError::at(input, line, format!("a row {} cells wide", width)));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Grid::from_vec(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[self.offset(pos)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        if self.contains(pos) {
+            let offset = self.offset(pos);
+            Some(&mut self.cells[offset])
+        } else {
+            None
+        }
+    }
+
+    /// As [`Grid::get`], for a point that might be off any side of the grid, negative coordinates included.
+    pub fn at(&self, point: Point) -> Option<&T> {
+        self.get(point.to_index()?)
+    }
+
+    pub fn at_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.get_mut(point.to_index()?)
+    }
+
+    /// Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell, row by row
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every cell along with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        self.positions().zip(self.cells.iter_mut())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() refuses a zero size, and a zero-width grid has no rows worth visiting anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is outside a grid {} wide", x, self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// The positions directly above, right, below and left of `pos` that are on the grid. The iterator doesn't borrow
+    /// the grid, so it's fine to update the neighbours while walking them.
+    pub fn neighbours_4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        neighbours(pos, (self.width, self.height), &Direction::CARDINAL)
+    }
+
+    /// As [`Grid::neighbours_4`], but diagonals count too.
+    pub fn neighbours_8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        neighbours(pos, (self.width, self.height), &Direction::ALL)
+    }
+
+    /// A `width` by `height` window onto part of the grid, with its own positions starting from `origin`.
+    pub fn region(&self, origin: (usize, usize), width: usize, height: usize) -> View<'_, T> {
+        View::new(self, origin, width, height)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn offset(&self, (x, y): (usize, usize)) -> usize {
+        y * self.width + x
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// A grid with every cell set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Swap rows for columns, so `(x, y)` moves to `(y, x)`.
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+}
+
+impl<T: From<u8>> Grid<T> {
+    /// Parse a block of single digits, like a heightmap or a map of risk levels.
+    pub fn parse_digits(input: &str) -> Result<Grid<T>, ParseError> {
+        Grid::parse(input, "a digit", |c| c.to_digit(10).map(|digit| T::from(digit as u8)))
+    }
+}
+
+impl Grid<char> {
+    pub fn parse_chars(input: &str) -> Result<Grid<char>, ParseError> {
+        Grid::parse(input, "a character", Some)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        assert!(self.contains(pos), "{:?} is outside a {}x{} grid", pos, self.width, self.height);
+        &self.cells[self.offset(pos)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        assert!(self.contains(pos), "{:?} is outside a {}x{} grid", pos, self.width, self.height);
+        let offset = self.offset(pos);
+        &mut self.cells[offset]
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        let (width, height) = (self.width, self.height);
+        self.at(point).unwrap_or_else(|| panic!("{} is outside a {}x{} grid", point, width, height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.at_mut(point).unwrap_or_else(|| panic!("{} is outside a {}x{} grid", point, width, height))
+    }
+}
+
+/// One line per row, with each cell written straight after the last. Cells that need spacing (or a different look
+/// altogether) can be mapped to something else first.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn neighbours(
+    pos: (usize, usize),
+    (width, height): (usize, usize),
+    directions: &'static [Direction],
+) -> impl Iterator<Item = (usize, usize)> {
+    let from = Point::from(pos);
+
+    directions.iter().filter_map(move |&direction| {
+        let (x, y) = from.step(direction).to_index()?;
+
+        if x < width && y < height {
+            Some((x, y))
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+        123
+        456
+    "#;
+
+    #[test]
+    fn parse_digits() {
+        let grid: Grid<u32> = Grid::parse_digits(SAMPLE).unwrap();
+
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(1, grid[(0, 0)]);
+        assert_eq!(3, grid[(2, 0)]);
+        assert_eq!(4, grid[(0, 1)]);
+        assert_eq!(None, grid.get((3, 0)));
+        assert_eq!(None, grid.get((0, 2)));
+    }
+
+    #[test]
+    fn parse_chars() {
+        let grid = Grid::parse_chars("#.\n.#\n").unwrap();
+
+        assert_eq!('#', grid[(0, 0)]);
+        assert_eq!('.', grid[(1, 0)]);
+        assert_eq!("#.\n.#\n", grid.to_string());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Grid::<u8>::parse_digits("123\n4x6\n").unwrap_err();
+        assert_eq!(ParseError::new(2, 2, "x", "a digit"), error);
+
+        let error = Grid::parse_chars("  ###\n  ##\n").unwrap_err();
+        assert_eq!(ParseError::new(2, 3, "##", "a row 3 cells wide"), error);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid: Grid<u32> = Grid::parse_digits(SAMPLE).unwrap();
+
+        assert_eq!(vec![&[1, 2, 3][..], &[4, 5, 6][..]], grid.rows().collect::<Vec<_>>());
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(vec![&2, &5], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(vec![5, 7, 9], grid.columns().map(|c| c.sum::<u32>()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn positions_are_row_major() {
+        let grid: Grid<u32> = Grid::parse_digits(SAMPLE).unwrap();
+
+        assert_eq!(vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)], grid.positions().collect::<Vec<_>>());
+        assert_eq!(Some(((1, 1), &5)), grid.iter().nth(4));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert_eq!(vec![(1, 0), (0, 1)], grid.neighbours_4((0, 0)).collect::<Vec<_>>());
+        assert_eq!(vec![(1, 0), (2, 1), (1, 2), (0, 1)], grid.neighbours_4((1, 1)).collect::<Vec<_>>());
+        assert_eq!(vec![(1, 0), (1, 1), (0, 1)], grid.neighbours_8((0, 0)).collect::<Vec<_>>());
+        assert_eq!(8, grid.neighbours_8((1, 1)).count());
+        assert_eq!(3, grid.neighbours_8((2, 2)).count());
+    }
+
+    #[test]
+    fn points() {
+        let mut grid: Grid<u32> = Grid::parse_digits(SAMPLE).unwrap();
+
+        assert_eq!(6, grid[Point::new(2, 1)]);
+        assert_eq!(Some(&2), grid.at(Point::new(1, 0)));
+        assert_eq!(None, grid.at(Point::new(-1, 0)));
+        assert_eq!(None, grid.at(Point::new(3, 1)));
+
+        grid[Point::new(0, 1)] = 0;
+        assert_eq!("123\n056\n", grid.to_string());
+    }
+
+    #[test]
+    fn update_neighbours() {
+        let mut grid = Grid::new(3, 3, 0);
+
+        for pos in grid.neighbours_8((0, 0)) {
+            grid[pos] += 1;
+        }
+
+        assert_eq!("010\n110\n000\n", grid.to_string());
+    }
+
+    #[test]
+    fn transpose() {
+        let grid: Grid<u32> = Grid::parse_digits(SAMPLE).unwrap();
+        let transposed = grid.transpose();
+
+        assert_eq!(2, transposed.width());
+        assert_eq!(3, transposed.height());
+        assert_eq!("14\n25\n36\n", transposed.to_string());
+        assert_eq!(grid, transposed.transpose());
+    }
+
+    #[test]
+    fn from_fn_and_map() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + y * 10);
+
+        assert_eq!(12, grid[(2, 1)]);
+        assert_eq!("..#\n...\n", grid.map(|&n| if n == 2 { '#' } else { '.' }).to_string());
+    }
+}
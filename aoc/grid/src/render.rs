@@ -0,0 +1,299 @@
+//! Pictures of grids, for watching a simulation rather than reading it off the terminal.
+//!
+//! A [`Frame`] is a grid with a colour picked for every cell, one pixel each. An [`Animation`] strings frames together
+//! and saves them as a PNG (just the last frame) or an animated GIF (all of them), scaled up so even the examples come
+//! out big enough to see.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use crate::Grid;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+/// Small grids are blown up until their longer side is at least this many pixels
+const MIN_SIDE: usize = 256;
+
+/// How long each frame of a GIF is shown, in hundredths of a second. The last one lingers before it loops.
+const FRAME_DELAY: u16 = 10;
+const LAST_FRAME_DELAY: u16 = 200;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Frame {
+    /// Colour in every cell of a grid
+    pub fn of<T>(grid: &Grid<T>, colour: impl Fn(&T) -> Rgb) -> Frame {
+        Frame {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.cells().map(colour).collect(),
+        }
+    }
+
+    /// Colour in each position, for pictures that need more than a cell to go on
+    pub fn from_fn(width: usize, height: usize, colour: impl FnMut((usize, usize)) -> Rgb) -> Frame {
+        let Grid { cells, .. } = Grid::from_fn(width, height, colour);
+
+        Frame { width, height, pixels: cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<Rgb> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// The frame as RGB bytes on a `width` by `height` canvas, every cell a `scale` pixel square. Frames smaller than
+    /// the canvas sit in its top left corner on black.
+    fn paint(&self, scale: usize, width: usize, height: usize) -> Vec<Rgb> {
+        let mut canvas = vec![BLACK; width * height];
+
+        for y in 0..(self.height * scale).min(height) {
+            for x in 0..(self.width * scale).min(width) {
+                canvas[y * width + x] = self.pixels[(y / scale) * self.width + x / scale];
+            }
+        }
+
+        canvas
+    }
+}
+
+/// A run of frames, all shown at the same scale
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Animation {
+    frames: Vec<Frame>,
+}
+
+impl Animation {
+    pub fn new() -> Animation {
+        Animation::default()
+    }
+
+    pub fn push(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Save as a PNG of the last frame or a GIF of every frame, depending on the file's extension.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if self.frames.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "There's nothing to draw"));
+        }
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => self.save_png(path),
+            Some("gif") => self.save_gif(path),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{} should end in .png or .gif", path.display()))),
+        }
+    }
+
+    fn save_png(&self, path: &Path) -> io::Result<()> {
+        let last = self.frames.last().expect("Checked there's a frame");
+        let (scale, width, height) = canvas(std::slice::from_ref(last));
+
+        let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&last.paint(scale, width, height).concat())?;
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    fn save_gif(&self, path: &Path) -> io::Result<()> {
+        let (scale, width, height) = canvas(&self.frames);
+        let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(width), u16::try_from(height)) else {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("A {}x{} picture is too big for a GIF", width, height)));
+        };
+
+        let mut encoder = gif::Encoder::new(BufWriter::new(File::create(path)?), gif_width, gif_height, &[]).map_err(io::Error::other)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+
+        for (i, frame) in self.frames.iter().enumerate() {
+            let mut gif_frame = indexed(gif_width, gif_height, &frame.paint(scale, width, height));
+            gif_frame.delay = if i + 1 == self.frames.len() { LAST_FRAME_DELAY } else { FRAME_DELAY };
+            encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The scale and canvas size that fit the biggest of the frames
+fn canvas(frames: &[Frame]) -> (usize, usize, usize) {
+    let width = frames.iter().map(|f| f.width).max().unwrap_or(0).max(1);
+    let height = frames.iter().map(|f| f.height).max().unwrap_or(0).max(1);
+    let scale = (MIN_SIDE / width.max(height)).max(1);
+
+    (scale, width * scale, height * scale)
+}
+
+/// A GIF frame in the picture's own colours when there are few enough of them to fit a palette, as there are for
+/// nearly every day. Anything more colourful gets quantised.
+fn indexed(width: u16, height: u16, pixels: &[Rgb]) -> gif::Frame<'static> {
+    let mut palette: HashMap<Rgb, u8> = HashMap::new();
+    let mut colours = Vec::new();
+    let mut indices = Vec::with_capacity(pixels.len());
+
+    for pixel in pixels {
+        let index = match palette.get(pixel) {
+            Some(&index) => index,
+            None if colours.len() < 256 => {
+                let index = colours.len() as u8;
+                palette.insert(*pixel, index);
+                colours.push(*pixel);
+                index
+            }
+            None => return gif::Frame::from_rgb_speed(width, height, &pixels.concat(), 10),
+        };
+        indices.push(index);
+    }
+
+    gif::Frame::from_palette_pixels(width, height, indices, colours.concat(), None)
+}
+
+/// Part way from one colour to another, `t` running from 0 to 1
+pub fn gradient(from: Rgb, to: Rgb, t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0);
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+
+    [mix(from[0], to[0]), mix(from[1], to[1]), mix(from[2], to[2])]
+}
+
+/// A bright colour for the `n`th of any number of things (basins, say), with neighbouring numbers far apart in hue
+pub fn distinct(n: usize) -> Rgb {
+    // Stepping round the colour wheel by the golden angle never lands near where it's been recently
+    let hue = (n as f64 * 0.618_033_988_75).fract() * 6.0;
+    let x = 1.0 - (hue % 2.0 - 1.0).abs();
+
+    let (r, g, b) = match hue as u8 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    let channel = |c: f64| (55.0 + c * 200.0) as u8;
+
+    [channel(r), channel(g), channel(b)]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn temp_file(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("aoc-render-{}-{}", std::process::id(), name))
+    }
+
+    fn checkerboard(width: usize, height: usize) -> Frame {
+        Frame::of(&Grid::from_fn(width, height, |(x, y)| (x + y) % 2 == 0), |&on| if on { WHITE } else { BLACK })
+    }
+
+    #[test]
+    fn frames() {
+        let frame = checkerboard(3, 2);
+
+        assert_eq!((3, 2), (frame.width(), frame.height()));
+        assert_eq!(Some(WHITE), frame.get((0, 0)));
+        assert_eq!(Some(BLACK), frame.get((1, 0)));
+        assert_eq!(Some(BLACK), frame.get((0, 1)));
+        assert_eq!(None, frame.get((3, 0)));
+        assert_eq!(frame, Frame::from_fn(3, 2, |(x, y)| if (x + y) % 2 == 0 { WHITE } else { BLACK }));
+
+        // Doubled up on a canvas with a spare row
+        let painted = frame.paint(2, 6, 5);
+        assert_eq!(&[WHITE, WHITE, BLACK, BLACK, WHITE, WHITE], &painted[0..6]);
+        assert_eq!(&painted[0..6], &painted[6..12]);
+        assert_eq!(vec![BLACK; 6], painted[24..30].to_vec());
+    }
+
+    #[test]
+    fn colours() {
+        assert_eq!(BLACK, gradient(BLACK, WHITE, 0.0));
+        assert_eq!([128, 128, 128], gradient(BLACK, WHITE, 0.5));
+        assert_eq!(WHITE, gradient(BLACK, WHITE, 7.0));
+
+        let colours: Vec<Rgb> = (0..20).map(distinct).collect();
+        for (i, colour) in colours.iter().enumerate() {
+            assert!(!colours[i + 1..].contains(colour), "Colour {} comes round again", i);
+        }
+    }
+
+    #[test]
+    fn pngs() {
+        let path = temp_file("checkerboard.png");
+        let mut animation = Animation::new();
+        animation.push(checkerboard(20, 20));
+        animation.push(checkerboard(4, 2));
+        animation.save(&path).unwrap();
+
+        let decoder = png::Decoder::new(File::open(&path).unwrap());
+        let reader = decoder.read_info().unwrap();
+        // Only the last frame, scaled up to 256 pixels across however big the others were
+        assert_eq!((256, 128), (reader.info().width, reader.info().height));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn gifs() {
+        let path = temp_file("checkerboard.gif");
+        let mut animation = Animation::new();
+        for size in (1..=4).rev() {
+            animation.push(checkerboard(size, size));
+        }
+        animation.save(&path).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(File::open(&path).unwrap()).unwrap();
+        assert_eq!((256, 256), (decoder.width(), decoder.height()));
+
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((256, 256), (frame.width, frame.height));
+            // Every frame starts with a white square in the corner
+            assert_eq!(&[255, 255, 255, 255], &frame.buffer[0..4]);
+            frames += 1;
+        }
+        assert_eq!(4, frames);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn refuses_other_formats() {
+        let mut animation = Animation::new();
+        assert!(animation.save(&temp_file("empty.png")).is_err());
+
+        animation.push(checkerboard(1, 1));
+        assert!(animation.save(&temp_file("checkerboard.jpg")).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt;
+use std::ops::Index;
+
+use crate::Grid;
+
+/// A rectangular window onto part of a [`Grid`]. Positions are relative to the window's top left corner, so code that
+/// works on a whole grid works just as well on a slice of one.
+#[derive(Debug)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    origin: (usize, usize),
+    width: usize,
+    height: usize,
+}
+
+// Derived Clone and Copy would insist on T: Clone, but a view only ever copies its reference
+impl<T> Clone for View<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for View<'_, T> {}
+
+impl<'a, T> View<'a, T> {
+    pub(crate) fn new(grid: &'a Grid<T>, origin: (usize, usize), width: usize, height: usize) -> View<'a, T> {
+        assert!(
+            origin.0 + width <= grid.width() && origin.1 + height <= grid.height(),
+            "A {}x{} region at {:?} doesn't fit in a {}x{} grid",
+            width,
+            height,
+            origin,
+            grid.width(),
+            grid.height()
+        );
+
+        View { grid, origin, width, height }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&'a T> {
+        if self.contains(pos) {
+            self.grid.get(self.grid_pos(pos))
+        } else {
+            None
+        }
+    }
+
+    /// Every position in the view, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell along with its position in the view, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        let (grid, (x0, y0)) = (self.grid, self.origin);
+        self.positions().map(move |(x, y)| ((x, y), &grid[(x0 + x, y0 + y)]))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let (x, y) = self.origin;
+        let (grid, width) = (self.grid, self.width);
+        (y..y + self.height).map(move |row| &grid.row(row)[x..x + width])
+    }
+
+    fn grid_pos(&self, (x, y): (usize, usize)) -> (usize, usize) {
+        (self.origin.0 + x, self.origin.1 + y)
+    }
+}
+
+impl<T: Clone> View<'_, T> {
+    /// Copy the view out into a grid of its own
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |pos| self[pos].clone())
+    }
+}
+
+impl<T> Index<(usize, usize)> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        assert!(self.contains(pos), "{:?} is outside a {}x{} view", pos, self.width, self.height);
+        &self.grid[self.grid_pos(pos)]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for View<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Grid;
+
+    const SAMPLE: &str = r#"
+        1234
+        5678
+        9012
+    "#;
+
+    #[test]
+    fn region() {
+        let grid: Grid<u32> = Grid::parse_digits(SAMPLE).unwrap();
+        let view = grid.region((1, 1), 2, 2);
+
+        assert_eq!(2, view.width());
+        assert_eq!(2, view.height());
+        assert_eq!(6, view[(0, 0)]);
+        assert_eq!(1, view[(1, 1)]);
+        assert_eq!(None, view.get((2, 0)));
+        assert_eq!("67\n01\n", view.to_string());
+        assert_eq!(vec![6, 7, 0, 1], view.iter().map(|(_, &n)| n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn to_grid() {
+        let grid: Grid<u32> = Grid::parse_digits(SAMPLE).unwrap();
+        let corner = grid.region((2, 0), 2, 3).to_grid();
+
+        assert_eq!(Grid::parse_digits("34\n78\n12").unwrap(), corner);
+    }
+
+    #[test]
+    #[should_panic]
+    fn region_must_fit() {
+        let grid: Grid<u32> = Grid::parse_digits(SAMPLE).unwrap();
+        grid.region((3, 0), 2, 1);
+    }
+}
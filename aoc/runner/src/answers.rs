@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::days::Day;
+use crate::Result;
+
+/// The answers we know are right for each day's official input, one table per day keyed like `2021-01`.
+#[derive(Debug, Default, PartialEq)]
+pub struct KnownAnswers {
+    days: BTreeMap<String, [Option<String>; 2]>,
+}
+
+impl KnownAnswers {
+    /// A missing file just means we don't know any answers yet.
+    pub fn load(path: &Path) -> Result<KnownAnswers> {
+        if !path.exists() {
+            return Ok(KnownAnswers::default());
+        }
+
+        KnownAnswers::parse(&fs::read_to_string(path)?).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    fn parse(text: &str) -> Result<KnownAnswers> {
+        let parsed: toml::Table = text.parse()?;
+        let mut known = KnownAnswers::default();
+
+        for (key, parts) in parsed {
+            let parts = parts.as_table().ok_or_else(|| format!("{} should be a table of answers", key))?;
+            let answer = |part: &str| -> Result<Option<String>> {
+                match parts.get(part) {
+                    None => Ok(None),
+                    Some(toml::Value::String(answer)) => Ok(Some(answer.clone())),
+                    Some(toml::Value::Integer(answer)) => Ok(Some(answer.to_string())),
+                    Some(_) => Err(format!("{}.{} should be a string or a number", key, part).into()),
+                }
+            };
+
+            let answers = [answer("part_1")?, answer("part_2")?];
+            known.days.insert(key, answers);
+        }
+
+        Ok(known)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_toml())?;
+        Ok(())
+    }
+
+    /// Answers are always written as strings, so big numbers survive and every answer looks the same. Pictures (day
+    /// 13) become multi-line literals that read just like the terminal output.
+    fn to_toml(&self) -> String {
+        let mut toml = String::from("# Answers for each day's official input, checked by `aoc verify`\n");
+
+        for (key, parts) in &self.days {
+            write!(toml, "\n[\"{}\"]\n", key).unwrap();
+            for (part, answer) in parts.iter().enumerate() {
+                match answer {
+                    Some(answer) if answer.contains('\n') => {
+                        writeln!(toml, "part_{} = '''\n{}'''", part + 1, answer).unwrap();
+                    }
+                    Some(answer) => writeln!(toml, "part_{} = {}", part + 1, toml::Value::from(answer.as_str())).unwrap(),
+                    None => (),
+                }
+            }
+        }
+
+        toml
+    }
+
+    pub fn get(&self, day: &Day, part: u8) -> Option<&str> {
+        self.days.get(&day.key())?[part as usize - 1].as_deref()
+    }
+
+    pub fn set(&mut self, day: &Day, part: u8, answer: String) {
+        self.days.entry(day.key()).or_default()[part as usize - 1] = Some(answer);
+    }
+}
+
+/// How one part's output compares with what we know.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, actual: Option<String> },
+    /// We have nothing to compare with, though the day may well have given an answer
+    Missing(Option<String>),
+}
+
+impl Verdict {
+    pub fn check(expected: Option<&str>, actual: Option<&String>) -> Verdict {
+        match expected {
+            None => Verdict::Missing(actual.cloned()),
+            Some(expected) if actual.is_some_and(|a| a == expected) => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+                actual: actual.cloned(),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "✅ pass"),
+            Verdict::Fail { .. } => write!(f, "❌ fail"),
+            Verdict::Missing(_) => write!(f, "❓ missing"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn day(year: u16, day: u8) -> Day {
+        Day {
+            year,
+            day,
+            package: "test".to_string(),
+            workspace: Default::default(),
+            dir: Default::default(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut known = KnownAnswers::default();
+        known.set(&day(2021, 13), 1, "647".to_string());
+        known.set(&day(2021, 13), 2, "#..#\n.##.".to_string());
+        known.set(&day(2021, 6), 2, "1740449478328".to_string());
+
+        let toml = known.to_toml();
+        assert!(toml.contains("[\"2021-06\"]\npart_2 = \"1740449478328\"\n"));
+        assert!(toml.contains("[\"2021-13\"]\npart_1 = \"647\"\npart_2 = '''\n#..#\n.##.'''\n"));
+
+        let parsed = KnownAnswers::parse(&toml).unwrap();
+        assert_eq!(known, parsed);
+        assert_eq!(Some("#..#\n.##."), parsed.get(&day(2021, 13), 2));
+        assert_eq!(None, parsed.get(&day(2021, 6), 1));
+        assert_eq!(None, parsed.get(&day(2022, 1), 1));
+    }
+
+    #[test]
+    fn numbers_are_answers_too() {
+        let known = KnownAnswers::parse("[\"2022-01\"]\npart_1 = 69883\n").unwrap();
+        assert_eq!(Some("69883"), known.get(&day(2022, 1), 1));
+
+        assert!(KnownAnswers::parse("[\"2022-01\"]\npart_1 = [1, 2]\n").is_err());
+    }
+
+    #[test]
+    fn every_day_has_known_answers() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let known = KnownAnswers::load(&root.join("answers.toml")).unwrap();
+
+        for day in crate::days::discover(&root).unwrap() {
+            for part in [1, 2] {
+                assert!(known.get(&day, part).is_some(), "No known answer for {} part {}", day, part);
+            }
+        }
+    }
+
+    #[test]
+    fn verdicts() {
+        let answer = |a: &str| Some(a.to_string());
+
+        assert_eq!(Verdict::Pass, Verdict::check(Some("7"), answer("7").as_ref()));
+        assert_eq!(
+            Verdict::Fail { expected: "7".to_string(), actual: answer("8") },
+            Verdict::check(Some("7"), answer("8").as_ref())
+        );
+        assert_eq!(Verdict::Fail { expected: "7".to_string(), actual: None }, Verdict::check(Some("7"), None));
+        assert_eq!(Verdict::Missing(answer("8")), Verdict::check(None, answer("8").as_ref()));
+    }
+}
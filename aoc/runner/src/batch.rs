@@ -0,0 +1,348 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::answers::Verdict;
+use crate::bench::Timings;
+use crate::exec::{Answers, Run};
+use crate::Result;
+
+/// How to write out a batch.
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+/// What one input gave, or why it gave nothing.
+#[derive(Debug, Default, PartialEq)]
+pub struct Row {
+    /// The input, as it was named on the command line
+    pub file: String,
+    pub answers: Answers,
+    pub timings: Timings,
+    /// Wall clock time for the whole run, process and all
+    pub elapsed: Option<Duration>,
+    pub error: Option<String>,
+    /// What each part should have been, when there's anything to compare with
+    pub expected: Option<[Option<String>; 2]>,
+}
+
+impl Row {
+    pub fn new(file: &Path, run: Result<Run>) -> Row {
+        let file = file.display().to_string();
+
+        match run {
+            Ok(run) => Row { file, answers: run.answers, timings: run.timings, elapsed: Some(run.elapsed), ..Default::default() },
+            Err(e) => Row { file, error: Some(e.to_string()), ..Default::default() },
+        }
+    }
+
+    fn verdicts(&self) -> Option<[Verdict; 2]> {
+        let expected = self.expected.as_ref()?;
+        Some([1, 2].map(|part| Verdict::check(expected[part as usize - 1].as_deref(), self.answers.get(part))))
+    }
+
+    /// The row as a whole: failed if either part did, missing if neither failed but one has nothing to compare with
+    pub fn verdict(&self) -> Option<&'static str> {
+        let verdicts = self.verdicts()?;
+
+        if verdicts.iter().any(|v| matches!(v, Verdict::Fail { .. })) {
+            Some("fail")
+        } else if verdicts.iter().any(|v| matches!(v, Verdict::Missing(_))) {
+            Some("missing")
+        } else {
+            Some("pass")
+        }
+    }
+
+    /// Every column, in order, as `(name, value)`. Answers are always text, so big numbers and pictures survive.
+    fn columns(&self) -> Vec<(&'static str, Value)> {
+        let nanos = |time: Option<Duration>| time.map_or(Value::Null, |t| Value::Number(t.as_nanos()));
+        let text = |text: Option<&String>| text.map_or(Value::Null, |t| Value::Text(t.clone()));
+
+        let mut columns = vec![
+            ("file", Value::Text(self.file.clone())),
+            ("part_1", text(self.answers.get(1))),
+            ("part_2", text(self.answers.get(2))),
+            ("parse_ns", nanos(self.timings.parse)),
+            ("part_1_ns", nanos(self.timings.part_1)),
+            ("part_2_ns", nanos(self.timings.part_2)),
+            ("total_ns", nanos(self.elapsed)),
+            ("error", text(self.error.as_ref())),
+        ];
+
+        if let Some(expected) = &self.expected {
+            columns.push(("expected_part_1", text(expected[0].as_ref())));
+            columns.push(("expected_part_2", text(expected[1].as_ref())));
+            columns.push(("verdict", self.verdict().map_or(Value::Null, |v| Value::Text(v.to_string()))));
+        }
+
+        columns
+    }
+}
+
+enum Value {
+    Null,
+    Number(u128),
+    Text(String),
+}
+
+/// The rows as a table with a header line. Every row has the same columns, so the expected answers are only there if
+/// they were asked for.
+pub fn write(rows: &[Row], format: Format) -> String {
+    match format {
+        Format::Csv => to_csv(rows),
+        Format::Json => to_json(rows),
+    }
+}
+
+fn to_csv(rows: &[Row]) -> String {
+    let mut csv = String::new();
+
+    if let Some(first) = rows.first() {
+        let names: Vec<&str> = first.columns().iter().map(|&(name, _)| name).collect();
+        writeln!(csv, "{}", names.join(",")).unwrap();
+    }
+
+    for row in rows {
+        let values: Vec<String> = row
+            .columns()
+            .into_iter()
+            .map(|(_, value)| match value {
+                Value::Null => String::new(),
+                Value::Number(n) => n.to_string(),
+                Value::Text(text) => csv_field(&text),
+            })
+            .collect();
+        writeln!(csv, "{}", values.join(",")).unwrap();
+    }
+
+    csv
+}
+
+/// Quoted only when it has to be: for commas, quotes and the line breaks in pictures
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// An array of objects, one per row, with `null` for anything missing
+fn to_json(rows: &[Row]) -> String {
+    let objects: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let fields: Vec<String> = row
+                .columns()
+                .into_iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        Value::Null => "null".to_string(),
+                        Value::Number(n) => n.to_string(),
+                        Value::Text(text) => json_string(&text),
+                    };
+                    format!("{}: {}", json_string(name), value)
+                })
+                .collect();
+            format!("  {{{}}}", fields.join(", "))
+        })
+        .collect();
+
+    match objects.is_empty() {
+        true => "[]\n".to_string(),
+        false => format!("[\n{}\n]\n", objects.join(",\n")),
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+    json
+}
+
+/// Answers to compare a batch with, from a CSV with `file`, `part_1` and `part_2` columns (any others are ignored), so
+/// one batch's output can be checked against another's. Inputs are matched by file name alone, as teammates keep
+/// theirs in different places.
+#[derive(Debug, Default, PartialEq)]
+pub struct Expected {
+    files: BTreeMap<String, [Option<String>; 2]>,
+}
+
+impl Expected {
+    pub fn load(path: &Path) -> Result<Expected> {
+        Expected::parse(&fs::read_to_string(path)?).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    fn parse(text: &str) -> Result<Expected> {
+        let mut records = parse_csv(text)?.into_iter();
+        let header = records.next().ok_or("There's no header line")?;
+
+        let column = |name: &str| header.iter().position(|h| h == name);
+        let file = column("file").ok_or("There's no file column")?;
+        let (part_1, part_2) = (column("part_1"), column("part_2"));
+        if part_1.is_none() && part_2.is_none() {
+            return Err("There's no part_1 or part_2 column".into());
+        }
+
+        let mut expected = Expected::default();
+        for record in records {
+            let answer = |column: Option<usize>| column.and_then(|c| record.get(c)).filter(|a| !a.is_empty()).cloned();
+            let Some(name) = record.get(file).map(|f| file_name(f)) else {
+                continue;
+            };
+
+            expected.files.insert(name, [answer(part_1), answer(part_2)]);
+        }
+
+        Ok(expected)
+    }
+
+    pub fn get(&self, file: &str) -> [Option<String>; 2] {
+        self.files.get(&file_name(file)).cloned().unwrap_or_default()
+    }
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path).file_name().map_or(path.to_string(), |n| n.to_string_lossy().to_string())
+}
+
+/// Records of fields, RFC 4180 style: fields with commas, quotes or line breaks in are quoted, with any quotes inside
+/// doubled. Blank lines are skipped.
+fn parse_csv(text: &str) -> Result<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if field.is_empty() => quoted = true,
+            (',', false) => record.push(std::mem::take(&mut field)),
+            ('\r', false) => (),
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field));
+                if record != [""] {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+            }
+            (c, _) => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err("A quoted field never ends".into());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn row(file: &str, part_1: &str, part_2: &str) -> Row {
+        Row {
+            file: file.to_string(),
+            answers: Answers { part_1: Some(part_1.to_string()), part_2: Some(part_2.to_string()) },
+            timings: Timings { parse: Some(Duration::from_nanos(1200)), part_1: Some(Duration::from_nanos(300)), part_2: None },
+            elapsed: Some(Duration::from_millis(4)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn csv() {
+        let crashed = Row { file: "inputs/day09/bob.txt".to_string(), error: Some("exited with 1: line 3, column 1".to_string()), ..Default::default() };
+        let rows = [row("inputs/day09/alice.txt", "15", "#..#\n.##."), crashed];
+
+        assert_eq!(
+            "file,part_1,part_2,parse_ns,part_1_ns,part_2_ns,total_ns,error\n\
+             inputs/day09/alice.txt,15,\"#..#\n.##.\",1200,300,,4000000,\n\
+             inputs/day09/bob.txt,,,,,,,\"exited with 1: line 3, column 1\"\n",
+            write(&rows, Format::Csv)
+        );
+    }
+
+    #[test]
+    fn json() {
+        let mut row = row("alice.txt", "15", "say \"hi\"");
+        row.expected = Some([Some("15".to_string()), None]);
+
+        assert_eq!(
+            "[\n  {\"file\": \"alice.txt\", \"part_1\": \"15\", \"part_2\": \"say \\\"hi\\\"\", \"parse_ns\": 1200, \
+             \"part_1_ns\": 300, \"part_2_ns\": null, \"total_ns\": 4000000, \"error\": null, \"expected_part_1\": \"15\", \
+             \"expected_part_2\": null, \"verdict\": \"missing\"}\n]\n",
+            write(&[row], Format::Json)
+        );
+        assert_eq!("[]\n", write(&[], Format::Json));
+    }
+
+    #[test]
+    fn verdicts() {
+        let mut row = row("alice.txt", "15", "1134");
+        assert_eq!(None, row.verdict());
+
+        row.expected = Some([Some("15".to_string()), Some("1134".to_string())]);
+        assert_eq!(Some("pass"), row.verdict());
+
+        row.expected = Some([Some("15".to_string()), Some("1135".to_string())]);
+        assert_eq!(Some("fail"), row.verdict());
+
+        row.expected = Some([None, None]);
+        assert_eq!(Some("missing"), row.verdict());
+    }
+
+    #[test]
+    fn expected_answers() {
+        let text = "file,part_1,part_2,error\r\n/home/alice/day09.txt,15,1134,\n\nbob/picture.txt,,\"#..#\n.##.\",\n";
+        let expected = Expected::parse(text).unwrap();
+
+        assert_eq!([Some("15".to_string()), Some("1134".to_string())], expected.get("inputs/day09.txt"));
+        assert_eq!([None, Some("#..#\n.##.".to_string())], expected.get("picture.txt"));
+        assert_eq!([None, None], expected.get("carol.txt"));
+
+        assert!(Expected::parse("name,part_1\nalice.txt,15").is_err());
+        assert!(Expected::parse("file,answer\nalice.txt,15").is_err());
+        assert!(Expected::parse("file,part_1\n\"alice.txt,15").is_err());
+    }
+
+    #[test]
+    fn batches_read_back() {
+        let mut rows = [row("a.txt", "1", "say \"hi\", then\nbye"), row("b.txt", "3", "4")];
+        let expected = Expected::parse(&write(&rows, Format::Csv)).unwrap();
+
+        for row in rows.iter_mut() {
+            row.expected = Some(expected.get(&row.file));
+            assert_eq!(Some("pass"), row.verdict());
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::days::Day;
+use crate::Result;
+
+/// Changes smaller than this are noise whatever percentage they come to
+const NOISE_FLOOR: Duration = Duration::from_micros(20);
+
+/// How long one day took to parse its input and solve each part, as the median of several runs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Timings {
+    pub parse: Option<Duration>,
+    pub part_1: Option<Duration>,
+    pub part_2: Option<Duration>,
+}
+
+impl Timings {
+    /// Pick out the `Bench parse: <ns>` and `Bench part N: <ns>` lines a day prints when given `--bench`.
+    pub fn parse(stdout: &str) -> Timings {
+        let mut timings = Timings::default();
+
+        for line in stdout.lines() {
+            let Some((phase, nanos)) = line.strip_prefix("Bench ").and_then(|l| l.split_once(": ")) else {
+                continue;
+            };
+            let Ok(nanos) = nanos.trim().parse::<u64>() else {
+                continue;
+            };
+
+            let time = Some(Duration::from_nanos(nanos));
+            match phase {
+                "parse" => timings.parse = time,
+                "part 1" => timings.part_1 = time,
+                "part 2" => timings.part_2 = time,
+                _ => (),
+            }
+        }
+
+        timings
+    }
+
+    fn phases(&self) -> [(&'static str, Option<Duration>); 3] {
+        [("parse", self.parse), ("part_1", self.part_1), ("part_2", self.part_2)]
+    }
+}
+
+/// How a timing compares with its baseline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Change {
+    /// Nothing to compare against
+    New,
+    Same(f64),
+    Faster(f64),
+    Slower(f64),
+}
+
+impl Change {
+    /// `threshold` is the fraction either way (0.1 for 10%) that counts as a real change
+    pub fn between(baseline: Option<Duration>, current: Duration, threshold: f64) -> Change {
+        let Some(baseline) = baseline.filter(|b| !b.is_zero()) else {
+            return Change::New;
+        };
+
+        let ratio = current.as_secs_f64() / baseline.as_secs_f64() - 1.0;
+        if current.abs_diff(baseline) < NOISE_FLOOR || ratio.abs() <= threshold {
+            Change::Same(ratio)
+        } else if ratio < 0.0 {
+            Change::Faster(ratio)
+        } else {
+            Change::Slower(ratio)
+        }
+    }
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::New => write!(f, "new"),
+            Change::Same(ratio) => write!(f, "{:+.0}%", ratio * 100.0),
+            Change::Faster(ratio) => write!(f, "{:+.0}% 🚀", ratio * 100.0),
+            Change::Slower(ratio) => write!(f, "{:+.0}% 🐢", ratio * 100.0),
+        }
+    }
+}
+
+/// The timings we compare against, one table per day keyed like `2021-01`. Times are stored in microseconds so the
+/// file reads (and diffs) sensibly.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    days: BTreeMap<String, Timings>,
+}
+
+impl Baseline {
+    /// A missing file is just an empty baseline; everything will show up as new.
+    pub fn load(path: &Path) -> Result<Baseline> {
+        if !path.exists() {
+            return Ok(Baseline::default());
+        }
+
+        Baseline::parse(&fs::read_to_string(path)?).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    fn parse(text: &str) -> Result<Baseline> {
+        let parsed: toml::Table = text.parse()?;
+        let mut baseline = Baseline::default();
+
+        for (key, phases) in parsed {
+            let phases = phases.as_table().ok_or_else(|| format!("{} should be a table of timings", key))?;
+            let micros = |phase: &str| -> Result<Option<Duration>> {
+                match phases.get(phase) {
+                    None => Ok(None),
+                    Some(value) => match value.as_float().or_else(|| value.as_integer().map(|i| i as f64)) {
+                        Some(micros) if micros >= 0.0 => Ok(Some(Duration::from_nanos((micros * 1000.0).round() as u64))),
+                        _ => Err(format!("{}.{} should be a number of microseconds", key, phase).into()),
+                    },
+                }
+            };
+
+            let timings = Timings {
+                parse: micros("parse")?,
+                part_1: micros("part_1")?,
+                part_2: micros("part_2")?,
+            };
+            baseline.days.insert(key, timings);
+        }
+
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, self.to_toml())?;
+        Ok(())
+    }
+
+    fn to_toml(&self) -> String {
+        let mut toml = String::from("# Median times in microseconds, written by `aoc bench --save`\n");
+
+        for (key, timings) in &self.days {
+            write!(toml, "\n[\"{}\"]\n", key).unwrap();
+            for (phase, time) in timings.phases() {
+                if let Some(time) = time {
+                    writeln!(toml, "{} = {:.3}", phase, time.as_secs_f64() * 1e6).unwrap();
+                }
+            }
+        }
+
+        toml
+    }
+
+    pub fn get(&self, day: &Day) -> Option<&Timings> {
+        self.days.get(&day.key())
+    }
+
+    pub fn set(&mut self, day: &Day, timings: Timings) {
+        self.days.insert(day.key(), timings);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn day(year: u16, day: u8) -> Day {
+        Day {
+            year,
+            day,
+            package: "test".to_string(),
+            workspace: Default::default(),
+            dir: Default::default(),
+        }
+    }
+
+    #[test]
+    fn parse_timings() {
+        let timings = Timings::parse("Part 1: 7\nBench parse: 1500\nBench part 1: 20000\nBench part 2: oops\n");
+
+        assert_eq!(
+            Timings {
+                parse: Some(Duration::from_nanos(1500)),
+                part_1: Some(Duration::from_micros(20)),
+                part_2: None,
+            },
+            timings
+        );
+    }
+
+    #[test]
+    fn changes() {
+        let ms = Duration::from_millis;
+
+        assert_eq!(Change::New, Change::between(None, ms(5), 0.1));
+        assert_eq!(Change::New, Change::between(Some(Duration::ZERO), ms(5), 0.1));
+        assert_eq!("+5%", Change::between(Some(ms(100)), ms(105), 0.1).to_string());
+        assert_eq!("+50% 🐢", Change::between(Some(ms(100)), ms(150), 0.1).to_string());
+        assert_eq!("-50% 🚀", Change::between(Some(ms(100)), ms(50), 0.1).to_string());
+
+        // Tripling a few microseconds is still just noise
+        let us = Duration::from_micros;
+        assert!(matches!(Change::between(Some(us(5)), us(15), 0.1), Change::Same(_)));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.set(
+            &day(2021, 14),
+            Timings {
+                parse: Some(Duration::from_micros(12)),
+                part_1: Some(Duration::from_micros(3500)),
+                part_2: None,
+            },
+        );
+        baseline.set(&day(2021, 1), Timings { part_2: Some(Duration::from_nanos(900)), ..Default::default() });
+
+        let toml = baseline.to_toml();
+        assert!(toml.contains("[\"2021-01\"]\npart_2 = 0.900\n"));
+        assert!(toml.contains("[\"2021-14\"]\nparse = 12.000\npart_1 = 3500.000\n"));
+
+        assert_eq!(baseline, Baseline::parse(&toml).unwrap());
+    }
+
+    #[test]
+    fn bad_baselines() {
+        assert!(Baseline::parse("[\"2021-01\"]\nparse = \"slow\"\n").is_err());
+        assert!(Baseline::parse("\"2021-01\" = 5\n").is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_core::input;
+
+use crate::Result;
+
+/// A single day's solver, as found in one of the year workspaces.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub package: String,
+    /// The year's workspace, e.g. `2021/`
+    pub workspace: PathBuf,
+    /// The crate itself, e.g. `2021/day-01/depthr/`
+    pub dir: PathBuf,
+}
+
+impl Day {
+    /// How checked-in files (the answers, the benchmark baseline) refer to this day, e.g. `2021-01`
+    pub fn key(&self) -> String {
+        format!("{}-{:02}", self.year, self.day)
+    }
+
+    /// The repository holding this day's year workspace
+    pub fn root(&self) -> &Path {
+        self.workspace.parent().unwrap_or(Path::new("."))
+    }
+
+    /// The official puzzle input, from `inputs/<year>/<day>.txt`
+    pub fn default_input(&self) -> Result<PathBuf> {
+        let path = input::official(self.root(), self.year, self.day);
+        if !path.is_file() {
+            return Err(format!("No input for {} day {} at {}; pass one with --input", self.year, self.day, path.display()).into());
+        }
+
+        Ok(path)
+    }
+
+    /// The puzzle's title, from the `//! 2021 day 1: Sonar Sweep` line each day's library starts with, or the package
+    /// name for a day that doesn't say
+    pub fn name(&self) -> String {
+        let title = fs::read_to_string(self.dir.join("src/lib.rs")).ok().and_then(|lib| {
+            let first = lib.lines().next()?.strip_prefix("//!")?;
+            first.split_once(": ").map(|(_, title)| title.trim().to_string()).filter(|t| !t.is_empty())
+        });
+
+        title.unwrap_or_else(|| self.package.clone())
+    }
+
+    /// One of the fixtures in the crate's `fixtures/` directory, by name
+    pub fn example(&self, name: &str) -> Result<PathBuf> {
+        Ok(input::example(self.root(), self.year, self.day, name)?)
+    }
+}
+
+impl std::fmt::Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {:2} ({})", self.year, self.day, self.package)
+    }
+}
+
+/// Find every day in every year workspace below `root`. A year is any directory named like `2021` with a workspace
+/// manifest, and its days are the `day-NN/...` members listed there.
+pub fn discover(root: &Path) -> Result<Vec<Day>> {
+    let mut days: Vec<Day> = Vec::new();
+
+    for entry in fs::read_dir(root)? {
+        let workspace = entry?.path();
+        let year = match workspace.file_name().and_then(|n| n.to_str()).and_then(|n| n.parse::<u16>().ok()) {
+            Some(year) => year,
+            None => continue,
+        };
+
+        let manifest = workspace.join("Cargo.toml");
+        if !manifest.is_file() {
+            continue;
+        }
+
+        for member in workspace_members(&manifest)? {
+            let day = match day_number(&member) {
+                Some(day) => day,
+                None => continue,
+            };
+
+            let dir = workspace.join(&member);
+            let package = package_name(&dir.join("Cargo.toml"))?;
+
+            days.push(Day {
+                year,
+                day,
+                package,
+                workspace: workspace.clone(),
+                dir,
+            });
+        }
+    }
+
+    days.sort_by_key(|d| (d.year, d.day));
+    Ok(days)
+}
+
+fn workspace_members(manifest: &Path) -> Result<Vec<String>> {
+    let parsed: toml::Table = fs::read_to_string(manifest)?.parse()?;
+
+    let members = parsed
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .ok_or_else(|| format!("{} has no workspace members", manifest.display()))?;
+
+    Ok(members.iter().filter_map(|m| m.as_str()).map(|m| m.to_string()).collect())
+}
+
+fn package_name(manifest: &Path) -> Result<String> {
+    let parsed: toml::Table = fs::read_to_string(manifest)?.parse()?;
+
+    parsed
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+        .map(|n| n.to_string())
+        .ok_or_else(|| format!("{} has no package name", manifest.display()).into())
+}
+
+/// `day-09/low-point` -> 9
+fn day_number(member: &str) -> Option<u8> {
+    let first = member.split('/').next()?;
+    first.strip_prefix("day-")?.parse::<u8>().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn repo_root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+    }
+
+    #[test]
+    fn day_numbers() {
+        assert_eq!(Some(1), day_number("day-01/depthr"));
+        assert_eq!(Some(10), day_number("day-10"));
+        assert_eq!(None, day_number("shared/day-10"));
+        assert_eq!(None, day_number("day-ten"));
+    }
+
+    #[test]
+    fn discovers_both_years() {
+        let days = discover(&repo_root()).unwrap();
+
+        let depthr = days.iter().find(|d| d.package == "depthr").unwrap();
+        assert_eq!((2021, 1), (depthr.year, depthr.day));
+
+        let calories = days.iter().find(|d| d.package == "calories").unwrap();
+        assert_eq!((2022, 1), (calories.year, calories.day));
+
+        let mut sorted = days.clone();
+        sorted.sort_by_key(|d| (d.year, d.day));
+        assert_eq!(sorted, days);
+    }
+
+    #[test]
+    fn names() {
+        let days = discover(&repo_root()).unwrap();
+
+        assert_eq!("Sonar Sweep", days.iter().find(|d| d.package == "depthr").unwrap().name());
+        assert_eq!("Rock Paper Scissors", days.iter().find(|d| d.package == "rock-paper-scissors").unwrap().name());
+    }
+
+    #[test]
+    fn inputs() {
+        let days = discover(&repo_root()).unwrap();
+        let day = |package: &str| days.iter().find(|d| d.package == package).unwrap();
+
+        for day in &days {
+            assert!(day.default_input().is_ok(), "{} has no official input", day);
+        }
+
+        assert!(day("low-point").default_input().unwrap().ends_with("inputs/2021/09.txt"));
+        assert!(day("calories").default_input().unwrap().ends_with("inputs/2022/01.txt"));
+        assert!(day("bingr").example("bingo-test").unwrap().ends_with("bingr/fixtures/bingo-test.txt"));
+        assert!(day("bingr").example("bingo").is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::bench::Timings;
+use crate::days::Day;
+use crate::Result;
+
+/// What a day's binary reported for each part.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    /// Every day prints `Part 1: <answer>` and `Part 2: <answer>` amongst whatever else it feels like printing. An
+    /// answer that starts on the line after its label (day 13's folded paper) runs until the next label, or until
+    /// the `Bench` timings that follow the answers. Trailing whitespace is never part of an answer, wherever it ends.
+    pub fn parse(stdout: &str) -> Answers {
+        let mut answers = Answers::default();
+        let mut current: Option<(u8, Vec<&str>)> = None;
+
+        for line in stdout.lines() {
+            let labelled = [(1, "Part 1:"), (2, "Part 2:")]
+                .into_iter()
+                .find_map(|(part, label)| line.strip_prefix(label).map(|rest| (part, rest.trim())));
+
+            match labelled {
+                Some((part, rest)) => {
+                    if let Some((part, lines)) = current.take() {
+                        answers.set(part, lines.join("\n"));
+                    }
+
+                    if rest.is_empty() {
+                        current = Some((part, Vec::new()));
+                    } else {
+                        answers.set(part, rest.to_string());
+                    }
+                }
+                None if line.starts_with("Bench ") => {
+                    if let Some((part, lines)) = current.take() {
+                        answers.set(part, lines.join("\n"));
+                    }
+                }
+                None => {
+                    if let Some((_, lines)) = current.as_mut() {
+                        lines.push(line);
+                    }
+                }
+            }
+        }
+
+        if let Some((part, lines)) = current {
+            answers.set(part, lines.join("\n"));
+        }
+
+        answers
+    }
+
+    pub fn get(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            _ => self.part_2.as_ref(),
+        }
+    }
+
+    fn set(&mut self, part: u8, answer: String) {
+        let answer = answer.trim_end().to_string();
+        match part {
+            1 => self.part_1 = Some(answer),
+            _ => self.part_2 = Some(answer),
+        }
+    }
+}
+
+/// What a day should read.
+#[derive(Debug)]
+pub enum Input {
+    File(PathBuf),
+    /// One of the day's fixtures, which the day strips of its answers itself
+    Example(PathBuf),
+    /// Made up by the day from a size and seed, and checked against whatever answers it was built with
+    Generated { size: usize, seed: u64 },
+}
+
+/// How to run a day, beyond which input to give it.
+#[derive(Debug, Default)]
+pub struct Options {
+    /// Only this part
+    pub part: Option<u8>,
+    /// One step of tracing per `-v`, shown as the day runs
+    pub verbosity: u8,
+    /// Have the day time itself over this many iterations
+    pub bench: Option<u32>,
+    /// Have the day draw itself to this .png or .gif
+    pub render: Option<PathBuf>,
+    /// Have the day play itself out on our stderr first, optionally with its own frame delay and without colour
+    pub animate: bool,
+    pub delay: Option<u64>,
+    pub no_colour: bool,
+    /// Have the day answer a line at a time as it reads the input
+    pub stream: bool,
+    /// Have the day count its simulation for this many steps, and with big integers
+    pub steps: Option<usize>,
+    pub big: bool,
+}
+
+/// The result of running one day against one input.
+#[derive(Debug)]
+pub struct Run {
+    pub answers: Answers,
+    pub elapsed: Duration,
+    /// Only filled in when benchmarking
+    pub timings: Timings,
+}
+
+/// Compile every day in the given year workspaces, in release mode so timings mean something.
+pub fn build(workspaces: &[&Path], package: Option<&str>) -> Result<()> {
+    for workspace in workspaces {
+        let mut cargo = Command::new(cargo());
+        cargo
+            .arg("build")
+            .arg("--release")
+            .arg("--quiet")
+            .arg("--manifest-path")
+            .arg(workspace.join("Cargo.toml"));
+
+        match package {
+            Some(package) => cargo.arg("-p").arg(package),
+            None => cargo.arg("--workspace"),
+        };
+
+        let status = cargo.status()?;
+        if !status.success() {
+            return Err(format!("cargo build failed for {}", workspace.display()).into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Run one day's tests (its fixtures included), showing cargo's output as it goes. Returns whether they all passed.
+pub fn test(day: &Day) -> Result<bool> {
+    let status = Command::new(cargo())
+        .arg("test")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(day.workspace.join("Cargo.toml"))
+        .arg("-p")
+        .arg(&day.package)
+        .status()?;
+
+    Ok(status.success())
+}
+
+/// Run an already built day against `input`. The path is passed along rather than piped in, so parse errors can name
+/// the file. Days skip the other part entirely when given one. When asked for traces they go straight to our stderr as
+/// they happen.
+pub fn run(day: &Day, input: &Input, options: &Options) -> Result<Run> {
+    let binary = binary(day);
+
+    let mut command = Command::new(&binary);
+    match input {
+        Input::File(path) if !path.is_file() => {
+            return Err(format!("Can't open {}: no such file", path.display()).into());
+        }
+        Input::File(path) => command.arg("--input").arg(path),
+        Input::Example(path) => command.arg("--example").arg(path.file_stem().unwrap_or_default()),
+        Input::Generated { size, seed } => command.arg("--generate").arg(size.to_string()).arg("--seed").arg(seed.to_string()),
+    };
+    if let Some(part) = options.part {
+        command.arg("--part").arg(part.to_string());
+    }
+    if options.verbosity > 0 {
+        command.arg(format!("-{}", "v".repeat(options.verbosity as usize)));
+    }
+    if let Some(iterations) = options.bench {
+        command.arg("--bench").arg(iterations.to_string());
+    }
+    if let Some(path) = &options.render {
+        command.arg("--render").arg(path);
+    }
+    if options.animate {
+        command.arg("--animate");
+    }
+    if let Some(delay) = options.delay {
+        command.arg("--delay").arg(delay.to_string());
+    }
+    if options.no_colour {
+        command.arg("--no-colour");
+    }
+    if options.stream {
+        command.arg("--stream");
+    }
+    if let Some(steps) = options.steps {
+        command.arg("--steps").arg(steps.to_string());
+    }
+    if options.big {
+        command.arg("--big");
+    }
+
+    let start = Instant::now();
+    let output = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(if options.verbosity > 0 || options.animate { Stdio::inherit() } else { Stdio::piped() })
+        .output()
+        .map_err(|e| format!("Can't run {}: {}", binary.display(), e))?;
+    let elapsed = start.elapsed();
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().find(|l| l.contains("panicked")).or_else(|| stderr.lines().last()).unwrap_or("see its output above");
+        return Err(format!("{} exited with {}: {}", day, output.status, reason).into());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(Run {
+        answers: Answers::parse(&stdout),
+        elapsed,
+        timings: Timings::parse(&stdout),
+    })
+}
+
+/// Have an already built day make up an input, returned as a fixture with whatever answers it was built with
+pub fn generate(day: &Day, size: usize, seed: u64) -> Result<String> {
+    let binary = binary(day);
+
+    let output = Command::new(&binary)
+        .arg("--generate")
+        .arg(size.to_string())
+        .arg("--seed")
+        .arg(seed.to_string())
+        .arg("--emit")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("Can't run {}: {}", binary.display(), e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{} exited with {}: {}", day, output.status, stderr.trim()).into());
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+fn binary(day: &Day) -> PathBuf {
+    target_dir(&day.workspace).join("release").join(&day.package)
+}
+
+fn cargo() -> String {
+    std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string())
+}
+
+fn target_dir(workspace: &Path) -> PathBuf {
+    match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => workspace.join("target"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_single_line_answers() {
+        let answers = Answers::parse("Advent of Code day 1! 🙌\nDepth INCREASED ☝🏻\nPart 1: 7\nchatter\nPart 2: 5\n");
+
+        assert_eq!(Some("7".to_string()), answers.part_1);
+        assert_eq!(Some("5".to_string()), answers.part_2);
+    }
+
+    #[test]
+    fn parse_multi_line_answer() {
+        let answers = Answers::parse("Part 1: 17\nFOLDING UP @ 7\nPart 2:\n#####\n#...#\n#####\n");
+
+        assert_eq!(Some("17".to_string()), answers.part_1);
+        assert_eq!(Some("#####\n#...#\n#####".to_string()), answers.part_2);
+
+        let answers = Answers::parse("Part 2:\n#####\n#####\nBench parse: 1200\nBench part 2: 5400\n");
+        assert_eq!(Some("#####\n#####".to_string()), answers.part_2);
+    }
+
+    #[test]
+    fn multi_line_answers_end_the_same_whatever_follows() {
+        let picture = Some("#.#\n.#.".to_string());
+
+        for stdout in ["Part 1:\n#.#\n.#.\n\n", "Part 1:\n#.#\n.#.\n\nPart 2: 5\n", "Part 1:\n#.#\n.#.  \n\nBench parse: 1200\n"] {
+            assert_eq!(picture, Answers::parse(stdout).part_1, "{:?}", stdout);
+        }
+    }
+
+    #[test]
+    fn parse_missing_answers() {
+        let answers = Answers::parse("Parsing <3,4,3,1,2>\nPart 1: 5934\n");
+
+        assert_eq!(Some("5934".to_string()), answers.part_1);
+        assert_eq!(None, answers.part_2);
+    }
+}
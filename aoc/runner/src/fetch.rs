@@ -0,0 +1,207 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_core::input;
+
+use crate::Result;
+
+/// The session cookie from a logged-in browser, which is what identifies whose input to download
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Where to download from, mostly so tests (or a mirror) can stand in for the real site
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const USER_AGENT: &str = concat!("aoc-runner/", env!("CARGO_PKG_VERSION"));
+
+/// How to reach the site. Settings come from a TOML file (`session = "…"`, `base_url = "…"`), and the environment
+/// wins over the file.
+#[derive(Debug, PartialEq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+}
+
+impl Config {
+    /// Read the config file, if there is one, then apply any settings from the environment.
+    pub fn load(path: &Path) -> Result<Config> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("{}: {}", path.display(), e).into()),
+        };
+
+        let env = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        Config::parse(&text, env(SESSION_ENV), env(BASE_URL_ENV)).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    fn parse(text: &str, session: Option<String>, base_url: Option<String>) -> Result<Config> {
+        let parsed: toml::Table = text.parse()?;
+        let setting = |key: &str| -> Result<Option<String>> {
+            match parsed.get(key) {
+                None => Ok(None),
+                Some(toml::Value::String(value)) => Ok(Some(value.trim().to_string())),
+                Some(_) => Err(format!("{} should be a string", key).into()),
+            }
+        };
+
+        Ok(Config {
+            session: session.or(setting("session")?),
+            base_url: base_url.or(setting("base_url")?).unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+        })
+    }
+
+    /// `$XDG_CONFIG_HOME/aoc/config.toml`, or `~/.config/aoc/config.toml`. Kept out of the repository, since the
+    /// session token is as good as a password.
+    pub fn default_path() -> PathBuf {
+        let config = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .unwrap_or_default();
+
+        config.join("aoc").join("config.toml")
+    }
+}
+
+/// Whether an input had to be downloaded
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Make sure a day's official input is at `inputs/<year>/<day>.txt`, downloading it if it isn't. An input that's
+/// already there is never fetched again: it can't change, and the site asks that it isn't hammered.
+pub fn fetch(root: &Path, year: u16, day: u8, config: &Config) -> Result<Fetched> {
+    let path = input::official(root, year, day);
+    if path.is_file() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let session = config
+        .session
+        .as_deref()
+        .ok_or_else(|| format!("No session token to download with; set {} or `session` in the config file", SESSION_ENV))?;
+
+    let text = download(&config.base_url, session, year, day)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, text)?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+fn download(base_url: &str, session: &str, year: u16, day: u8) -> Result<String> {
+    let url = format!("{}/{}/day/{}/input", base_url.trim_end_matches('/'), year, day);
+
+    let response = ureq::get(&url)
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", USER_AGENT)
+        .call();
+
+    match response {
+        Ok(response) => {
+            let text = response.into_string()?;
+            if text.trim().is_empty() {
+                return Err(format!("{} came back empty", url).into());
+            }
+            Ok(text)
+        }
+        Err(e) => Err(failure(&url, year, day, e).into()),
+    }
+}
+
+/// What went wrong talking to the site, in terms of what to do about it
+pub fn failure(url: &str, year: u16, day: u8, error: ureq::Error) -> String {
+    match error {
+        ureq::Error::Status(404, _) => format!("{} day {} isn't out yet ({} gave 404)", year, day, url),
+        ureq::Error::Status(400 | 401 | 403, _) => {
+            format!("{} turned the session token down; it has probably expired, so copy a fresh one from the browser", url)
+        }
+        ureq::Error::Status(status, _) => format!("{} gave {}", url, status),
+        e => format!("Couldn't reach {}: {}", url, e),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::Server;
+
+    /// Only the one good session gets an input, and only for 2015 day 1
+    fn site() -> Server {
+        Server::start(|request| match request.path.as_str() {
+            _ if !request.has_session("good") => ("400 Bad Request", "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".to_string()),
+            "/2015/day/1/input" => ("200 OK", "(()(()(\n".to_string()),
+            _ => ("404 Not Found", "404 Not Found\n".to_string()),
+        })
+    }
+
+    fn config(server: &Server, session: Option<&str>) -> Config {
+        Config {
+            session: session.map(|s| s.to_string()),
+            base_url: format!("{}/", server.url),
+        }
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn configs() {
+        assert_eq!(Config { session: None, base_url: DEFAULT_BASE_URL.to_string() }, Config::parse("", None, None).unwrap());
+
+        let file = "session = \"abc123\\n\"\nbase_url = \"http://localhost:8080\"\n";
+        assert_eq!(
+            Config { session: Some("abc123".to_string()), base_url: "http://localhost:8080".to_string() },
+            Config::parse(file, None, None).unwrap()
+        );
+
+        // The environment wins
+        let config = Config::parse(file, Some("from-env".to_string()), None).unwrap();
+        assert_eq!(Some("from-env"), config.session.as_deref());
+
+        assert!(Config::parse("session = 123", None, None).is_err());
+    }
+
+    #[test]
+    fn downloads_once_then_uses_the_cache() {
+        let server = site();
+        let root = temp_root("cache");
+
+        let path = input::official(&root, 2015, 1);
+        assert_eq!(Fetched::Downloaded(path.clone()), fetch(&root, 2015, 1, &config(&server, Some("good"))).unwrap());
+        assert_eq!("(()(()(\n", fs::read_to_string(&path).unwrap());
+
+        // Even without a session, a cached day never goes back to the server
+        assert_eq!(Fetched::Cached(path), fetch(&root, 2015, 1, &config(&server, None)).unwrap());
+        assert_eq!(vec!["/2015/day/1/input"], server.paths());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn explains_failures() {
+        let server = site();
+        let root = temp_root("failures");
+
+        let error = fetch(&root, 2015, 1, &config(&server, Some("stale"))).unwrap_err().to_string();
+        assert!(error.contains("session token"), "{}", error);
+
+        let error = fetch(&root, 2015, 2, &config(&server, Some("good"))).unwrap_err().to_string();
+        assert!(error.contains("isn't out yet"), "{}", error);
+
+        let error = fetch(&root, 2015, 3, &config(&server, None)).unwrap_err().to_string();
+        assert!(error.contains(SESSION_ENV), "{}", error);
+
+        // Nothing is cached when the download fails
+        assert!(!root.join("inputs").exists());
+        assert_eq!(vec!["/2015/day/1/input", "/2015/day/2/input"], server.paths());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
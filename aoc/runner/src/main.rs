@@ -0,0 +1,715 @@
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Parser, Subcommand};
+
+mod answers;
+mod batch;
+mod bench;
+mod days;
+mod exec;
+mod fetch;
+#[cfg(test)]
+mod mock;
+mod progress;
+mod scaffold;
+mod submit;
+mod watch;
+
+use answers::{KnownAnswers, Verdict};
+use batch::{Expected, Format, Row};
+use bench::{Baseline, Change};
+use days::Day;
+use exec::Input;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// One runner for every day of every year 🎄
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    /// Repository root holding the year workspaces. Defaults to the checkout this runner was built from.
+    #[arg(long, global = true)]
+    root: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, or every day with --all
+    Run(RunArgs),
+    /// Time parsing and each part of every day on its official input, against a saved baseline
+    Bench(BenchArgs),
+    /// Check every day still gives the known answers for its official input
+    Verify(VerifyArgs),
+    /// Start a new day: a crate wired up to the shared solver, with a test skeleton and an example fixture to fill in
+    New(NewArgs),
+    /// Rebuild a day whenever its files change, running its tests and then, if they pass, its official input
+    Watch(WatchArgs),
+    /// Download a day's official input into inputs/, unless it's already there
+    Fetch(FetchArgs),
+    /// Send an answer to the site, unless it's already known to be wrong
+    Submit(SubmitArgs),
+    /// Make up an input for a day, of any size, as a fixture with whatever answers it was built to have
+    Generate(GenerateArgs),
+    /// Solve one day for each of a set of inputs, such as everyone's personal ones, as a table of answers and timings
+    Batch(BatchArgs),
+    /// Rewrite the progress table in README.md: every day's name, which parts give the known answers, and how long
+    /// they take in the benchmark baseline
+    Readme(ReadmeArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[arg(long, required_unless_present = "all")]
+    year: Option<u16>,
+
+    #[arg(long, required_unless_present = "all")]
+    day: Option<u8>,
+
+    /// Only show this part's answer
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Puzzle input to use instead of the day's official one
+    #[arg(long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+
+    /// Use one of the day's fixtures, by name (e.g. sonar-depths-test)
+    #[arg(long, conflicts_with_all = ["all", "input"])]
+    example: Option<String>,
+
+    /// Run every day (of --year, if given) and print a summary table
+    #[arg(long, conflicts_with = "day")]
+    all: bool,
+
+    /// Solve a generated input of this size instead, failing if it doesn't give the answers it was built with
+    #[arg(long, conflicts_with_all = ["all", "input", "example"])]
+    generate: Option<usize>,
+
+    /// Seed for --generate. The same size and seed always give the same input.
+    #[arg(long, requires = "generate", default_value_t = 0)]
+    seed: u64,
+
+    /// Also draw the puzzle, to a .png of how it ends up or a .gif of how it gets there
+    #[arg(long, conflicts_with = "all")]
+    render: Option<PathBuf>,
+
+    /// Play the puzzle out in the terminal first, for the days with a simulation to watch
+    #[arg(long, conflicts_with = "all")]
+    animate: bool,
+
+    /// Milliseconds to show each frame of --animate for
+    #[arg(long, requires = "animate")]
+    delay: Option<u64>,
+
+    /// Animate without colours (NO_COLOR works too)
+    #[arg(long, requires = "animate")]
+    no_colour: bool,
+
+    /// Answer a line at a time as the input is read, for the days that can, so inputs bigger than memory go through
+    #[arg(long, conflicts_with_all = ["all", "render", "animate"])]
+    stream: bool,
+
+    /// Count the day's simulation (lanternfish, polymers) for this many steps instead of the puzzle's own number
+    #[arg(long, conflicts_with_all = ["all", "stream"])]
+    steps: Option<usize>,
+
+    /// Count the day's simulation with big integers rather than failing once a count overflows 64 bits
+    #[arg(long, conflicts_with_all = ["all", "stream"])]
+    big: bool,
+
+    /// Show the day's traces as it runs, more with each -v. AOC_LOG picks them out by module instead.
+    #[arg(short, long, action = clap::ArgAction::Count, conflicts_with = "all")]
+    verbose: u8,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[arg(long)]
+    year: Option<u16>,
+
+    #[arg(long)]
+    day: Option<u8>,
+
+    /// Runs of each phase to take the median of
+    #[arg(long, default_value_t = 10)]
+    iterations: u32,
+
+    /// Percentage change either way that counts as a regression or an improvement
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+
+    /// Baseline to compare against. Defaults to bench/baseline.toml in the repository.
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Record these timings as the new baseline
+    #[arg(long)]
+    save: bool,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    #[arg(long)]
+    year: Option<u16>,
+
+    #[arg(long)]
+    day: Option<u8>,
+
+    /// Known answers to check against. Defaults to answers.toml in the repository.
+    #[arg(long)]
+    answers: Option<PathBuf>,
+
+    /// Record the answers given for any part we don't know yet. Known answers are never overwritten.
+    #[arg(long)]
+    save: bool,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    year: u16,
+
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Package name, for a crate at <year>/day-NN/<name>. Defaults to a crate called day-NN at <year>/day-NN.
+    #[arg(long)]
+    name: Option<String>,
+
+    /// The puzzle's title (e.g. "Camp Cleanup"), which the README's progress table shows
+    #[arg(long)]
+    title: Option<String>,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    #[arg(long)]
+    year: u16,
+
+    #[arg(long)]
+    day: u8,
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    #[arg(long)]
+    year: u16,
+
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Settings file holding `session` and `base_url`. Defaults to ~/.config/aoc/config.toml; AOC_SESSION and
+    /// AOC_BASE_URL override it.
+    #[arg(long)]
+    config: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    #[arg(long)]
+    year: u16,
+
+    #[arg(long)]
+    day: u8,
+
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// The answer to send. Defaults to whatever the day gives for its official input.
+    answer: Option<String>,
+
+    /// Settings file holding `session` and `base_url`, as for fetch
+    #[arg(long)]
+    config: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    #[arg(long)]
+    year: u16,
+
+    #[arg(long)]
+    day: u8,
+
+    /// How big to make it. What that measures depends on the day: lines, boards, the side of a map...
+    #[arg(long)]
+    size: usize,
+
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Where to write it, e.g. into the day's fixtures/ to make it a test. Defaults to stdout.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct BatchArgs {
+    #[arg(long)]
+    year: u16,
+
+    #[arg(long)]
+    day: u8,
+
+    /// Puzzle inputs to solve, e.g. inputs/day09/*.txt
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    #[arg(long, value_enum, default_value_t = Format::Csv)]
+    format: Format,
+
+    /// Answers to compare with: a CSV with file, part_1 and part_2 columns, like an earlier batch's. Inputs are
+    /// matched by file name.
+    #[arg(long)]
+    expected: Option<PathBuf>,
+
+    /// Runs of each phase to take the median of
+    #[arg(long, default_value_t = 1)]
+    iterations: u32,
+
+    /// Where to write the table. Defaults to stdout.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ReadmeArgs {
+    /// Fail if the table is out of date instead of rewriting it, e.g. to guard a branch
+    #[arg(long)]
+    check: bool,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let root = cli.root.unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../.."));
+
+    let result = match cli.command {
+        Command::Run(args) => run(&root, args),
+        Command::Bench(args) => bench(&root, args),
+        Command::Verify(args) => verify(&root, args),
+        Command::New(args) => new(&root, args),
+        Command::Fetch(args) => fetch(&root, args),
+        Command::Submit(args) => submit(&root, args),
+        Command::Generate(args) => generate(&root, args),
+        Command::Batch(args) => batch(&root, args),
+        Command::Readme(args) => readme(&root, args),
+        Command::Watch(args) => select(&root, Some(args.year), Some(args.day)).and_then(|days| watch::watch(&days[0])),
+    };
+
+    if let Err(e) = result {
+        eprintln!("💥 {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Every day matching the (optional) year and day, or an error if there aren't any
+fn select(root: &Path, year: Option<u16>, day: Option<u8>) -> Result<Vec<Day>> {
+    let days: Vec<Day> = days::discover(root)?
+        .into_iter()
+        .filter(|d| year.is_none_or(|y| y == d.year))
+        .filter(|d| day.is_none_or(|n| n == d.day))
+        .collect();
+
+    if days.is_empty() {
+        let year = year.map_or("any year".to_string(), |y| y.to_string());
+        let day = day.map_or("any day".to_string(), |d| format!("day {}", d));
+        return Err(format!("No solver found for {}, {}", year, day).into());
+    }
+
+    Ok(days)
+}
+
+fn run(root: &Path, args: RunArgs) -> Result<()> {
+    let days = select(root, args.year, args.day)?;
+
+    let parts: Vec<u8> = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    if !args.all {
+        let day = &days[0];
+        exec::build(&[&day.workspace], Some(&day.package))?;
+
+        let input = match (args.generate, args.input, args.example) {
+            (Some(size), _, _) => Input::Generated { size, seed: args.seed },
+            (None, Some(input), _) => Input::File(input),
+            (None, None, Some(example)) => Input::Example(day.example(&example)?),
+            (None, None, None) => Input::File(day.default_input()?),
+        };
+
+        let options = exec::Options {
+            part: args.part,
+            verbosity: args.verbose,
+            render: args.render.clone(),
+            animate: args.animate,
+            delay: args.delay,
+            no_colour: args.no_colour,
+            stream: args.stream,
+            steps: args.steps,
+            big: args.big,
+            ..Default::default()
+        };
+        let run = exec::run(day, &input, &options)?;
+
+        println!("🎄 {}", day);
+        for part in parts {
+            match run.answers.get(part) {
+                Some(answer) if answer.contains('\n') => println!("Part {}:\n{}", part, answer),
+                Some(answer) => println!("Part {}: {}", part, answer),
+                None => println!("Part {}: (no answer)", part),
+            }
+        }
+        println!("⏱  {:?}", run.elapsed);
+        if let Some(path) = args.render {
+            println!("🖼  {}", path.display());
+        }
+
+        return Ok(());
+    }
+
+    let mut workspaces: Vec<&Path> = days.iter().map(|d| d.workspace.as_path()).collect();
+    workspaces.dedup();
+    exec::build(&workspaces, None)?;
+
+    print_summary(&days, &parts, args.part);
+    Ok(())
+}
+
+/// Time every selected day and compare with the baseline. Regressions fail the command (unless they're being saved as
+/// the new baseline), as does any day that can't be timed at all, so it can guard a branch.
+fn bench(root: &Path, args: BenchArgs) -> Result<()> {
+    let days = select(root, args.year, args.day)?;
+    let path = args.baseline.unwrap_or_else(|| root.join("bench").join("baseline.toml"));
+    let mut baseline = Baseline::load(&path)?;
+    let threshold = args.threshold / 100.0;
+
+    let mut workspaces: Vec<&Path> = days.iter().map(|d| d.workspace.as_path()).collect();
+    workspaces.dedup();
+    exec::build(&workspaces, None)?;
+
+    println!("{:<6}{:>4}  {:<24}{:<24}{:<24}", "Year", "Day", "Parse", "Part 1", "Part 2");
+
+    let mut regressions = 0;
+    let mut crashes = 0;
+    for day in &days {
+        print!("{:<6}{:>4}  ", day.year, day.day);
+
+        let options = exec::Options { bench: Some(args.iterations), ..Default::default() };
+        let timings = match day.default_input().and_then(|input| exec::run(day, &Input::File(input), &options)) {
+            Ok(run) => run.timings,
+            Err(e) => {
+                println!("💥 {}", e);
+                crashes += 1;
+                continue;
+            }
+        };
+
+        let before = baseline.get(day).cloned().unwrap_or_default();
+        for (time, baseline_time) in [(timings.parse, before.parse), (timings.part_1, before.part_1), (timings.part_2, before.part_2)] {
+            match time {
+                Some(time) => {
+                    let change = Change::between(baseline_time, time, threshold);
+                    if matches!(change, Change::Slower(_)) {
+                        regressions += 1;
+                    }
+                    print!("{:<24}", format!("{:.1?} ({})", time, change));
+                }
+                None => print!("{:<24}", "-"),
+            }
+        }
+        println!();
+
+        if args.save {
+            baseline.set(day, timings);
+        }
+    }
+
+    if args.save {
+        baseline.save(&path)?;
+        println!("📝 Saved {}", path.display());
+    } else if regressions > 0 {
+        return Err(format!("{} timings regressed by more than {}% against {}", regressions, args.threshold, path.display()).into());
+    }
+
+    if crashes > 0 {
+        return Err(format!("{} of {} days couldn't be timed", crashes, days.len()).into());
+    }
+
+    Ok(())
+}
+
+/// Run every selected day on its official input, and compare each part with the known answer. Anything wrong or
+/// crashing fails the command; parts we just don't know the answer to yet, or days we haven't got the input for, don't.
+fn verify(root: &Path, args: VerifyArgs) -> Result<()> {
+    let days = select(root, args.year, args.day)?;
+    let path = args.answers.unwrap_or_else(|| root.join("answers.toml"));
+    let mut known = KnownAnswers::load(&path)?;
+
+    let mut workspaces: Vec<&Path> = days.iter().map(|d| d.workspace.as_path()).collect();
+    workspaces.dedup();
+    exec::build(&workspaces, None)?;
+
+    println!("{:<6}{:>4}  {:<14}{:<14}", "Year", "Day", "Part 1", "Part 2");
+
+    let mut failures: Vec<String> = Vec::new();
+    let mut missing = 0;
+    let mut no_input = 0;
+    for day in &days {
+        print!("{:<6}{:>4}  ", day.year, day.day);
+
+        let Ok(input) = day.default_input() else {
+            println!("📭 no input");
+            no_input += 1;
+            continue;
+        };
+
+        let run = match exec::run(day, &Input::File(input), &exec::Options::default()) {
+            Ok(run) => run,
+            Err(e) => {
+                println!("💥 panic");
+                failures.push(format!("{}: {}", day, e));
+                continue;
+            }
+        };
+
+        for part in [1, 2] {
+            let verdict = Verdict::check(known.get(day, part), run.answers.get(part));
+            print!("{:<14}", verdict.to_string());
+
+            match verdict {
+                Verdict::Pass => (),
+                Verdict::Fail { expected, actual } => {
+                    let actual = actual.map_or("nothing".to_string(), |a| format!("{:?}", a));
+                    failures.push(format!("{} part {}: expected {:?} but got {}", day, part, expected, actual));
+                }
+                Verdict::Missing(actual) => {
+                    missing += 1;
+                    if let (true, Some(actual)) = (args.save, actual) {
+                        known.set(day, part, actual);
+                    }
+                }
+            }
+        }
+        println!();
+    }
+
+    if args.save && missing > 0 {
+        known.save(&path)?;
+        println!("📝 Recorded new answers in {}", path.display());
+    } else if missing > 0 {
+        println!("❓ {} parts have no known answer; record them with --save once they're right", missing);
+    }
+    if no_input > 0 {
+        println!("📭 {} days have no official input to check; get them with `aoc fetch`", no_input);
+    }
+
+    if !failures.is_empty() {
+        for failure in &failures {
+            eprintln!("❌ {}", failure);
+        }
+        return Err(format!("{} parts didn't match {}", failures.len(), path.display()).into());
+    }
+
+    Ok(())
+}
+
+/// Download a day's input, or say where the cached copy is
+fn fetch(root: &Path, args: FetchArgs) -> Result<()> {
+    let config = fetch::Config::load(&args.config.unwrap_or_else(fetch::Config::default_path))?;
+
+    match fetch::fetch(root, args.year, args.day, &config)? {
+        fetch::Fetched::Cached(path) => println!("📦 Already have {}", path.strip_prefix(root).unwrap_or(&path).display()),
+        fetch::Fetched::Downloaded(path) => println!("⬇️  Downloaded {}", path.strip_prefix(root).unwrap_or(&path).display()),
+    }
+
+    Ok(())
+}
+
+/// Submit an answer, keeping a log of what's been sent in submissions.toml. A correct answer goes into answers.toml
+/// too, so `verify` checks it from then on.
+fn submit(root: &Path, args: SubmitArgs) -> Result<()> {
+    let day = &select(root, Some(args.year), Some(args.day))?[0];
+
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => {
+            exec::build(&[&day.workspace], Some(&day.package))?;
+            let options = exec::Options { part: Some(args.part), ..Default::default() };
+            let run = exec::run(day, &Input::File(day.default_input()?), &options)?;
+            run.answers.get(args.part).cloned().ok_or_else(|| format!("{} gave no answer for part {}", day, args.part))?
+        }
+    };
+
+    let config = fetch::Config::load(&args.config.unwrap_or_else(fetch::Config::default_path))?;
+    let log_path = root.join("submissions.toml");
+    let mut log = submit::Log::load(&log_path)?;
+
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_secs();
+    let outcome = submit::submit(&config, &mut log, day, args.part, &answer, now);
+    log.save(&log_path)?;
+
+    let outcome = outcome?;
+    println!("🎄 {} part {}: {} → {}", day, args.part, answer.trim(), outcome);
+
+    if outcome == submit::Outcome::Correct {
+        let answers_path = root.join("answers.toml");
+        let mut known = KnownAnswers::load(&answers_path)?;
+        if known.get(day, args.part).is_none() {
+            known.set(day, args.part, answer.trim().to_string());
+            known.save(&answers_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a generated input out as a fixture, noting how to make it again
+fn generate(root: &Path, args: GenerateArgs) -> Result<()> {
+    let day = &select(root, Some(args.year), Some(args.day))?[0];
+    exec::build(&[&day.workspace], Some(&day.package))?;
+
+    let fixture = exec::generate(day, args.size, args.seed)?;
+    let text = format!("# aoc generate --year {} --day {} --size {} --seed {}\n{}", day.year, day.day, args.size, args.seed, fixture);
+
+    match args.output {
+        Some(path) => {
+            std::fs::write(&path, text)?;
+            eprintln!("📝 Wrote {}", path.display());
+        }
+        None => print!("{}", text),
+    }
+
+    Ok(())
+}
+
+/// Run a day on every input given, one row each. An input that won't solve gets its error in its row rather than
+/// stopping the rest, but fails the command once they're all done, as does any answer that's not what was expected.
+fn batch(root: &Path, args: BatchArgs) -> Result<()> {
+    let day = &select(root, Some(args.year), Some(args.day))?[0];
+    let expected = args.expected.as_deref().map(Expected::load).transpose()?;
+    exec::build(&[&day.workspace], Some(&day.package))?;
+
+    let options = exec::Options { bench: Some(args.iterations), ..Default::default() };
+    let rows: Vec<Row> = args
+        .inputs
+        .iter()
+        .map(|input| {
+            eprintln!("🎄 {}", input.display());
+            let mut row = Row::new(input, exec::run(day, &Input::File(input.clone()), &options));
+            row.expected = expected.as_ref().map(|expected| expected.get(&row.file));
+            row
+        })
+        .collect();
+
+    let table = batch::write(&rows, args.format);
+    match args.output {
+        Some(path) => {
+            std::fs::write(&path, table)?;
+            eprintln!("📝 Wrote {}", path.display());
+        }
+        None => print!("{}", table),
+    }
+
+    let errors = rows.iter().filter(|row| row.error.is_some()).count();
+    let wrong = rows.iter().filter(|row| row.verdict() == Some("fail")).count();
+    if errors + wrong > 0 {
+        return Err(format!("{} of {} inputs failed to solve and {} gave unexpected answers", errors, rows.len(), wrong).into());
+    }
+
+    Ok(())
+}
+
+/// Run every day on its official input and check it against the known answers, as `verify` does, then put the
+/// results in the README's progress table along with each day's baseline timings.
+fn readme(root: &Path, args: ReadmeArgs) -> Result<()> {
+    let days = select(root, None, None)?;
+    let known = KnownAnswers::load(&root.join("answers.toml"))?;
+    let baseline = Baseline::load(&root.join("bench").join("baseline.toml"))?;
+
+    let mut workspaces: Vec<&Path> = days.iter().map(|d| d.workspace.as_path()).collect();
+    workspaces.dedup();
+    exec::build(&workspaces, None)?;
+
+    let rows: Vec<progress::Row> = days
+        .iter()
+        .map(|day| {
+            eprintln!("🎄 {}", day);
+            let parts = match day.default_input() {
+                Err(_) => [progress::Status::NoInput, progress::Status::NoInput],
+                Ok(input) => match exec::run(day, &Input::File(input), &exec::Options::default()) {
+                    Ok(run) => [1, 2].map(|part| progress::Status::of(&Verdict::check(known.get(day, part), run.answers.get(part)))),
+                    Err(_) => [progress::Status::Crashed, progress::Status::Crashed],
+                },
+            };
+            progress::Row::new(day, parts, baseline.get(day))
+        })
+        .collect();
+
+    let path = root.join("README.md");
+    let readme = std::fs::read_to_string(&path)?;
+    let updated = progress::splice(&readme, &progress::table(&rows))?;
+
+    if updated == readme {
+        println!("✅ {} is up to date", path.display());
+    } else if args.check {
+        return Err(format!("{} is out of date; run `aoc readme` to update it", path.display()).into());
+    } else {
+        std::fs::write(&path, updated)?;
+        println!("📝 Updated {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Scaffold a new day and say what's left to do
+fn new(root: &Path, args: NewArgs) -> Result<()> {
+    let dir = scaffold::new_day(root, args.year, args.day, args.name.as_deref(), args.title.as_deref())?;
+    let shown = dir.strip_prefix(root).unwrap_or(&dir);
+
+    println!("🎄 Created {}, and added it to {}/Cargo.toml", shown.display(), args.year);
+    println!("   Paste the example into {}", shown.join("fixtures/example.txt").display());
+    println!("   and get the puzzle input with `aoc fetch --year {} --day {}`", args.year, args.day);
+    if args.title.is_none() {
+        println!("   The puzzle's title goes in place of {} at the top of {}", scaffold::UNTITLED, shown.join("src/lib.rs").display());
+    }
+
+    Ok(())
+}
+
+/// Run each day on its official input and tabulate the results. A failing day is reported in its row rather than
+/// stopping the rest.
+fn print_summary(days: &[Day], parts: &[u8], part: Option<u8>) {
+    print!("{:<6}{:>4}  ", "Year", "Day");
+    for part in parts {
+        print!("{:<20}", format!("Part {}", part));
+    }
+    println!("{:>10}", "Time");
+
+    for day in days {
+        print!("{:<6}{:>4}  ", day.year, day.day);
+
+        let options = exec::Options { part, ..Default::default() };
+        let run = day.default_input().and_then(|input| exec::run(day, &Input::File(input), &options));
+        match run {
+            Ok(run) => {
+                for part in parts {
+                    print!("{:<20}", summarise(run.answers.get(*part)));
+                }
+                println!("{:>10}", format!("{:.1?}", run.elapsed));
+            }
+            Err(e) => println!("💥 {}", e),
+        }
+    }
+}
+
+/// Keep the table to a single line per day, even for answers drawn as pictures
+fn summarise(answer: Option<&String>) -> String {
+    match answer {
+        Some(answer) if answer.contains('\n') => format!("({} lines)", answer.lines().count()),
+        Some(answer) => answer.clone(),
+        None => "-".to_string(),
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt::Write as _;
+
+use crate::answers::Verdict;
+use crate::bench::Timings;
+use crate::days::Day;
+use crate::Result;
+
+/// The README's progress table lives between these lines, and `aoc readme` replaces whatever is between them.
+pub const START: &str = "<!-- progress: written by `aoc readme`, so don't edit it by hand -->";
+pub const END: &str = "<!-- /progress -->";
+
+/// How far one part of a day has got.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Status {
+    /// Gives the known answer
+    Solved,
+    /// Gives an answer, but not the known one
+    Wrong,
+    /// Gives an answer there's nothing to check against yet
+    Unchecked,
+    /// Gives no answer at all
+    Unsolved,
+    /// Fell over before answering
+    Crashed,
+    /// Couldn't be run, for want of an official input
+    NoInput,
+}
+
+impl Status {
+    pub fn of(verdict: &Verdict) -> Status {
+        match verdict {
+            Verdict::Pass => Status::Solved,
+            Verdict::Fail { actual: Some(_), .. } => Status::Wrong,
+            Verdict::Missing(Some(_)) => Status::Unchecked,
+            Verdict::Fail { actual: None, .. } | Verdict::Missing(None) => Status::Unsolved,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Status::Solved => "⭐",
+            Status::Wrong => "❌",
+            Status::Unchecked => "❓",
+            Status::Unsolved => "",
+            Status::Crashed => "💥",
+            Status::NoInput => "-",
+        }
+    }
+}
+
+/// One line of the table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    pub name: String,
+    pub parts: [Status; 2],
+    /// Median times from the benchmark baseline, if the day has any
+    pub timings: Timings,
+}
+
+impl Row {
+    pub fn new(day: &Day, parts: [Status; 2], timings: Option<&Timings>) -> Row {
+        Row { year: day.year, day: day.day, name: day.name(), parts, timings: timings.cloned().unwrap_or_default() }
+    }
+}
+
+/// A table of days for each year, newest year first, with each part's status and median time and a count of the stars
+/// earned. Times come from the benchmark baseline, so they only change when it's saved.
+pub fn table(rows: &[Row]) -> String {
+    let mut years: Vec<u16> = rows.iter().map(|r| r.year).collect();
+    years.sort_unstable_by(|a, b| b.cmp(a));
+    years.dedup();
+
+    let mut table = String::new();
+    for year in years {
+        let rows: Vec<&Row> = rows.iter().filter(|r| r.year == year).collect();
+        let stars = rows.iter().flat_map(|r| &r.parts).filter(|&p| *p == Status::Solved).count();
+
+        writeln!(table, "### {} · {} ⭐\n", year, stars).unwrap();
+        writeln!(table, "| Day | Puzzle | Part 1 | Part 2 | Parse |").unwrap();
+        writeln!(table, "| ---: | --- | --- | --- | ---: |").unwrap();
+        for row in rows {
+            let part = |part: usize, time| cell(&row.parts[part], time);
+            writeln!(
+                table,
+                "| {} | [{}](https://adventofcode.com/{}/day/{}) | {} | {} | {} |",
+                row.day,
+                row.name,
+                row.year,
+                row.day,
+                part(0, row.timings.part_1),
+                part(1, row.timings.part_2),
+                row.timings.parse.map_or(String::new(), |t| format!("{:.1?}", t)),
+            )
+            .unwrap();
+        }
+        table.push('\n');
+    }
+
+    table.push_str("⭐ gives the known answer, ❓ gives an answer that hasn't been checked yet, ❌ gives the wrong one and 💥 ");
+    table.push_str("crashes. Times are medians from `aoc bench`, on whichever machine last saved the baseline.\n");
+
+    table
+}
+
+fn cell(status: &Status, time: Option<std::time::Duration>) -> String {
+    match (status.symbol(), time) {
+        (symbol, Some(time)) if !symbol.is_empty() && *status != Status::NoInput => format!("{} {:.1?}", symbol, time),
+        (symbol, _) => symbol.to_string(),
+    }
+}
+
+/// `readme` with everything between the progress markers replaced by `table`
+pub fn splice(readme: &str, table: &str) -> Result<String> {
+    let missing = || format!("There's nowhere to put the table; add a line with {} and another with {} where it should go", START, END);
+
+    let start = readme.find(START).ok_or_else(missing)? + START.len();
+    let end = start + readme[start..].find(END).ok_or_else(missing)?;
+
+    Ok(format!("{}\n\n{}\n{}", &readme[..start], table.trim_end(), &readme[end..]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    fn row(year: u16, day: u8, name: &str, parts: [Status; 2]) -> Row {
+        Row { year, day, name: name.to_string(), parts, timings: Timings::default() }
+    }
+
+    #[test]
+    fn statuses() {
+        let answer = |a: &str| Some(a.to_string());
+
+        assert_eq!(Status::Solved, Status::of(&Verdict::Pass));
+        assert_eq!(Status::Wrong, Status::of(&Verdict::Fail { expected: "7".to_string(), actual: answer("8") }));
+        assert_eq!(Status::Unsolved, Status::of(&Verdict::Fail { expected: "7".to_string(), actual: None }));
+        assert_eq!(Status::Unchecked, Status::of(&Verdict::Missing(answer("8"))));
+        assert_eq!(Status::Unsolved, Status::of(&Verdict::Missing(None)));
+    }
+
+    #[test]
+    fn tables() {
+        let mut sonar = row(2021, 1, "Sonar Sweep", [Status::Solved, Status::Solved]);
+        sonar.timings = Timings { parse: Some(Duration::from_nanos(36_864)), part_1: Some(Duration::from_nanos(2_528)), part_2: None };
+        let rows = [
+            sonar,
+            row(2021, 2, "Dive!", [Status::Wrong, Status::Crashed]),
+            row(2022, 1, "Calorie Counting", [Status::Unchecked, Status::Unsolved]),
+        ];
+
+        let table = table(&rows);
+        assert!(table.starts_with("### 2022 · 0 ⭐\n\n| Day | Puzzle | Part 1 | Part 2 | Parse |\n| ---: | --- | --- | --- | ---: |\n"), "{}", table);
+        assert!(table.contains("| 1 | [Calorie Counting](https://adventofcode.com/2022/day/1) | ❓ |  |  |\n"), "{}", table);
+        assert!(table.contains("### 2021 · 2 ⭐\n"), "{}", table);
+        assert!(table.contains("| 1 | [Sonar Sweep](https://adventofcode.com/2021/day/1) | ⭐ 2.5µs | ⭐ | 36.9µs |\n"), "{}", table);
+        assert!(table.contains("| 2 | [Dive!](https://adventofcode.com/2021/day/2) | ❌ | 💥 |  |\n"), "{}", table);
+    }
+
+    #[test]
+    fn splicing() {
+        let readme = format!("# Advent of Code\n\n## Progress\n\n{}\nold table\n{}\n\n## Running\n", START, END);
+
+        let spliced = splice(&readme, "new table\n").unwrap();
+        assert_eq!(format!("# Advent of Code\n\n## Progress\n\n{}\n\nnew table\n{}\n\n## Running\n", START, END), spliced);
+        assert_eq!(spliced, splice(&spliced, "new table\n").unwrap());
+
+        assert!(splice("# Advent of Code\n", "new table").is_err());
+        assert!(splice(&format!("{}\nold table\n", START), "new table").is_err());
+    }
+}
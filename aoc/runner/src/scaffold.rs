@@ -0,0 +1,167 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::days;
+use crate::Result;
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const BUILD_RS: &str = include_str!("../templates/build.rs.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tmpl");
+const MAIN_RS: &str = include_str!("../templates/main.rs.tmpl");
+const EXAMPLE: &str = include_str!("../templates/example.txt.tmpl");
+
+/// The title a new day gets when it isn't given one
+pub const UNTITLED: &str = "Untitled";
+
+/// Create a new day's crate from the templates, a library wired up to `aoc_core` with a test skeleton and a thin binary
+/// to run it, plus an empty example fixture, and add it to its year's workspace (creating that too for a new year).
+/// Without a `name` the crate is `<year>/day-NN` and called `day-NN`; with one it's `<year>/day-NN/<name>`. The library
+/// starts with the puzzle's `title`, for the README to show, or [`UNTITLED`] until it's filled in. Returns the new
+/// crate's directory.
+pub fn new_day(root: &Path, year: u16, day: u8, name: Option<&str>, title: Option<&str>) -> Result<PathBuf> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("There's no day {}; Advent of Code runs from day 1 to 25", day).into());
+    }
+
+    let day_dir = format!("day-{:02}", day);
+    let (member, package) = match name {
+        Some(name) => (format!("{}/{}", day_dir, name), name.to_string()),
+        None => (day_dir.clone(), day_dir),
+    };
+
+    if package.is_empty() || !package.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_') {
+        return Err(format!("{} won't do as a package name; stick to lowercase letters, digits, - and _", package).into());
+    }
+
+    if let Some(existing) = days::discover(root)?.into_iter().find(|d| d.year == year && d.day == day) {
+        return Err(format!("{} already exists at {}", existing, existing.dir.display()).into());
+    }
+
+    let workspace = root.join(year.to_string());
+    let dir = workspace.join(&member);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()).into());
+    }
+
+    let core = format!("{}aoc/core", "../".repeat(member.split('/').count() + 1));
+    let fill = |template: &str| {
+        template
+            .replace("{name}", &package)
+            .replace("{crate}", &package.replace('-', "_"))
+            .replace("{core}", &core)
+            .replace("{year}", &year.to_string())
+            .replace("{day}", &day.to_string())
+            .replace("{dd}", &format!("{:02}", day))
+            .replace("{title}", title.unwrap_or(UNTITLED))
+    };
+
+    fs::create_dir_all(dir.join("src"))?;
+    fs::create_dir_all(dir.join("fixtures"))?;
+    fs::write(dir.join("Cargo.toml"), fill(CARGO_TOML))?;
+    fs::write(dir.join("build.rs"), fill(BUILD_RS))?;
+    fs::write(dir.join("src/lib.rs"), fill(LIB_RS))?;
+    fs::write(dir.join("src/main.rs"), fill(MAIN_RS))?;
+    fs::write(dir.join("fixtures/example.txt"), fill(EXAMPLE))?;
+
+    let manifest = workspace.join("Cargo.toml");
+    let text = match fs::read_to_string(&manifest) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => "[workspace]\nmembers = [\n]\n".to_string(),
+        Err(e) => return Err(e.into()),
+    };
+    fs::write(&manifest, add_member(&text, &member)?)?;
+
+    Ok(dir)
+}
+
+/// Add `member` to a workspace manifest's `members`, keeping them in order. The list is rewritten one tab-indented
+/// member per line, as they're kept by hand; the rest of the manifest is left exactly as it was.
+fn add_member(manifest: &str, member: &str) -> Result<String> {
+    let parsed: toml::Table = manifest.parse()?;
+    let mut members: Vec<String> = parsed
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .ok_or("The workspace manifest has no members list")?
+        .iter()
+        .filter_map(|m| m.as_str())
+        .map(|m| m.to_string())
+        .collect();
+
+    if members.iter().any(|m| m == member) {
+        return Err(format!("{} is already a workspace member", member).into());
+    }
+    members.push(member.to_string());
+    members.sort();
+
+    let start = manifest.find("members").ok_or("The workspace manifest has no members list")?;
+    let end = start + manifest[start..].find(']').ok_or("The workspace members list isn't closed")? + 1;
+
+    let list: String = members.iter().map(|m| format!("\t\"{}\",\n", m)).collect();
+    Ok(format!("{}members = [\n{}]{}", &manifest[..start], list, &manifest[end..]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn adding_members() {
+        let manifest = "[workspace]\nmembers = [\n\t\"day-01/calories\",\n\t\"day-03/rucksacks\",\n]";
+
+        assert_eq!(
+            "[workspace]\nmembers = [\n\t\"day-01/calories\",\n\t\"day-02\",\n\t\"day-03/rucksacks\",\n]",
+            add_member(manifest, "day-02").unwrap()
+        );
+        assert!(add_member(manifest, "day-01/calories").is_err());
+        assert!(add_member("[package]\nname = \"x\"\n", "day-02").is_err());
+
+        // One-line lists come out the same as hand-kept ones
+        assert_eq!(
+            "[workspace]\nmembers = [\n\t\"day-01\",\n]\nresolver = \"2\"\n",
+            add_member("[workspace]\nmembers = []\nresolver = \"2\"\n", "day-01").unwrap()
+        );
+    }
+
+    #[test]
+    fn scaffolding() {
+        let root = std::env::temp_dir().join(format!("aoc-new-{}", std::process::id()));
+        fs::create_dir_all(root.join("2015")).unwrap();
+        fs::write(root.join("2015/Cargo.toml"), "[workspace]\nmembers = [\n]").unwrap();
+
+        let dir = new_day(&root, 2015, 7, Some("wires"), Some("Some Assembly Required")).unwrap();
+        assert_eq!(root.join("2015/day-07/wires"), dir);
+
+        let lib = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("impl Solution for Day07 {\n    const YEAR: u16 = 2015;\n    const DAY: u8 = 7;"));
+        assert!(lib.contains("aoc_core::fixture_tests!(Day07);"));
+        assert!(lib.starts_with("//! 2015 day 7: Some Assembly Required\n"));
+        assert!(!lib.contains("{dd}") && !lib.contains("{year}"));
+
+        let main = fs::read_to_string(dir.join("src/main.rs")).unwrap();
+        assert!(main.starts_with("use wires::Day07;\n"));
+
+        let cargo = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+        assert!(cargo.contains("name = \"wires\""));
+        assert!(cargo.contains("aoc-core = { path = \"../../../aoc/core\" }"));
+        assert!(dir.join("build.rs").is_file());
+        assert!(dir.join("fixtures/example.txt").is_file());
+
+        let days = days::discover(&root).unwrap();
+        assert_eq!(vec![(2015, 7, "wires".to_string())], days.iter().map(|d| (d.year, d.day, d.package.clone())).collect::<Vec<_>>());
+        assert_eq!("Some Assembly Required", days[0].name());
+
+        // A new year gets a workspace of its own, and flat days sit one level up
+        let dir = new_day(&root, 2016, 1, None, None).unwrap();
+        assert_eq!(root.join("2016/day-01"), dir);
+        assert!(fs::read_to_string(dir.join("src/lib.rs")).unwrap().starts_with("//! 2016 day 1: Untitled\n"));
+        assert!(fs::read_to_string(dir.join("Cargo.toml")).unwrap().contains("path = \"../../aoc/core\""));
+        assert_eq!("[workspace]\nmembers = [\n\t\"day-01\",\n]\n", fs::read_to_string(root.join("2016/Cargo.toml")).unwrap());
+
+        assert!(new_day(&root, 2015, 7, None, None).is_err(), "day 7 already exists");
+        assert!(new_day(&root, 2015, 26, None, None).is_err());
+        assert!(new_day(&root, 2015, 8, Some("Bad Name"), None).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
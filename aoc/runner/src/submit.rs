@@ -0,0 +1,402 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::days::Day;
+use crate::fetch::{self, Config};
+use crate::Result;
+
+/// What the site made of an answer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint as to which way
+    Wrong,
+    AlreadySolved,
+    /// Answered too recently; nothing was checked
+    RateLimited(Duration),
+}
+
+impl Outcome {
+    /// Read the outcome from the page the site sends back
+    pub fn parse(html: &str) -> Result<Outcome> {
+        let text = article(html);
+
+        if text.contains("That's the right answer") {
+            Ok(Outcome::Correct)
+        } else if text.contains("That's not the right answer") {
+            if text.contains("too high") {
+                Ok(Outcome::TooHigh)
+            } else if text.contains("too low") {
+                Ok(Outcome::TooLow)
+            } else {
+                Ok(Outcome::Wrong)
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Ok(Outcome::RateLimited(wait(&text).unwrap_or(Duration::from_secs(60))))
+        } else if text.contains("Did you already complete it") {
+            Ok(Outcome::AlreadySolved)
+        } else {
+            Err(format!("Can't make sense of the response: {}", text.trim()).into())
+        }
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "⭐️ Correct"),
+            Outcome::TooHigh => write!(f, "❌ Wrong: too high"),
+            Outcome::TooLow => write!(f, "❌ Wrong: too low"),
+            Outcome::Wrong => write!(f, "❌ Wrong"),
+            Outcome::AlreadySolved => write!(f, "✅ Already solved"),
+            Outcome::RateLimited(wait) => write!(f, "⏳ Too soon; try again in {}s", wait.as_secs()),
+        }
+    }
+}
+
+/// The text of the page's `<article>`, which is where the verdict is, without any markup
+fn article(html: &str) -> String {
+    let start = html.find("<article").unwrap_or(0);
+    let end = html[start..].find("</article>").map_or(html.len(), |end| start + end);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+
+    text
+}
+
+/// How long the site says to wait before answering again: "You have 1m 5s left to wait", or after a wrong answer,
+/// "Please wait one minute before trying again" and "please wait 5 minutes before trying again"
+fn wait(text: &str) -> Option<Duration> {
+    if let Some(end) = text.find(" left to wait") {
+        let start = text[..end].rfind("You have ")? + "You have ".len();
+        let mut seconds = 0;
+        for word in text[start..end].split_whitespace() {
+            let (number, unit) = word.split_at(word.find(|c: char| !c.is_ascii_digit())?);
+            let scale = match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+            seconds += number.parse::<u64>().ok()? * scale;
+        }
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let lower = text.to_lowercase();
+    let start = lower.find("please wait ")? + "please wait ".len();
+    let mut words = lower[start..].split_whitespace();
+    let count = match words.next()? {
+        "one" | "a" => 1,
+        n => n.parse::<u64>().ok()?,
+    };
+
+    match words.next()? {
+        unit if unit.starts_with("minute") => Some(Duration::from_secs(count * 60)),
+        unit if unit.starts_with("second") => Some(Duration::from_secs(count)),
+        _ => None,
+    }
+}
+
+/// Every answer given for one part, sorted by what the site said about it
+#[derive(Debug, Default, PartialEq)]
+struct Attempts {
+    correct: Option<String>,
+    wrong: Vec<String>,
+    too_high: Vec<String>,
+    too_low: Vec<String>,
+}
+
+/// Everything submitted so far, kept so the same wrong answer (or one already known to be out of bounds) is never
+/// sent twice, and so the site's waits are respected between runs. One table per part keyed like `2021-01`, plus when
+/// the next answer may be sent, in seconds since the Unix epoch.
+#[derive(Debug, Default, PartialEq)]
+pub struct Log {
+    not_before: Option<u64>,
+    parts: BTreeMap<(String, u8), Attempts>,
+}
+
+impl Log {
+    /// A missing file just means nothing has been submitted yet.
+    pub fn load(path: &Path) -> Result<Log> {
+        if !path.exists() {
+            return Ok(Log::default());
+        }
+
+        Log::parse(&fs::read_to_string(path)?).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    fn parse(text: &str) -> Result<Log> {
+        let parsed: toml::Table = text.parse()?;
+        let mut log = Log::default();
+
+        for (key, value) in parsed {
+            if key == "not_before" {
+                log.not_before = Some(value.as_integer().ok_or("not_before should be a number of seconds")? as u64);
+                continue;
+            }
+
+            let day = value.as_table().ok_or_else(|| format!("{} should be a table of parts", key))?;
+            for (part, attempts) in day {
+                let number = match part.as_str() {
+                    "part_1" => 1,
+                    "part_2" => 2,
+                    _ => return Err(format!("{}.{} isn't a part", key, part).into()),
+                };
+                let attempts = attempts.as_table().ok_or_else(|| format!("{}.{} should be a table", key, part))?;
+
+                let answers = |outcome: &str| -> Result<Vec<String>> {
+                    match attempts.get(outcome) {
+                        None => Ok(Vec::new()),
+                        Some(toml::Value::Array(answers)) => Ok(answers.iter().filter_map(|a| a.as_str()).map(|a| a.to_string()).collect()),
+                        Some(_) => Err(format!("{}.{}.{} should be a list of answers", key, part, outcome).into()),
+                    }
+                };
+
+                let parsed = Attempts {
+                    correct: attempts.get("correct").and_then(|a| a.as_str()).map(|a| a.to_string()),
+                    wrong: answers("wrong")?,
+                    too_high: answers("too_high")?,
+                    too_low: answers("too_low")?,
+                };
+                log.parts.insert((key.clone(), number), parsed);
+            }
+        }
+
+        Ok(log)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_toml())?;
+        Ok(())
+    }
+
+    fn to_toml(&self) -> String {
+        let mut toml = String::from("# Every answer sent by `aoc submit`, and what the site said\n");
+
+        if let Some(not_before) = self.not_before {
+            writeln!(toml, "not_before = {}", not_before).unwrap();
+        }
+
+        let list = |answers: &[String]| answers.iter().map(|a| toml::Value::from(a.as_str()).to_string()).collect::<Vec<_>>().join(", ");
+        for ((key, part), attempts) in &self.parts {
+            write!(toml, "\n[\"{}\".part_{}]\n", key, part).unwrap();
+            if let Some(correct) = &attempts.correct {
+                writeln!(toml, "correct = {}", toml::Value::from(correct.as_str())).unwrap();
+            }
+            for (outcome, answers) in [("wrong", &attempts.wrong), ("too_high", &attempts.too_high), ("too_low", &attempts.too_low)] {
+                if !answers.is_empty() {
+                    writeln!(toml, "{} = [{}]", outcome, list(answers)).unwrap();
+                }
+            }
+        }
+
+        toml
+    }
+
+    /// Why this answer shouldn't be sent right now, if there's a reason
+    fn refuse(&self, day: &Day, part: u8, answer: &str, now: u64) -> Option<String> {
+        let attempts = self.parts.get(&(day.key(), part));
+
+        if let Some(correct) = attempts.and_then(|a| a.correct.as_ref()) {
+            return Some(match correct == answer {
+                true => format!("{} was already accepted for part {}", answer, part),
+                false => format!("Part {} is already solved, with {}", part, correct),
+            });
+        }
+
+        if let Some(attempts) = attempts {
+            if attempts.wrong.iter().chain(&attempts.too_high).chain(&attempts.too_low).any(|a| a == answer) {
+                return Some(format!("{} has already been tried, and it's wrong", answer));
+            }
+
+            if let Ok(number) = answer.parse::<i128>() {
+                let numbers = |answers: &[String]| answers.iter().filter_map(|a| a.parse::<i128>().ok()).collect::<Vec<_>>();
+                if let Some(high) = numbers(&attempts.too_high).into_iter().filter(|h| number >= *h).min() {
+                    return Some(format!("{} can't be right: {} was already too high", answer, high));
+                }
+                if let Some(low) = numbers(&attempts.too_low).into_iter().filter(|l| number <= *l).max() {
+                    return Some(format!("{} can't be right: {} was already too low", answer, low));
+                }
+            }
+        }
+
+        match self.not_before {
+            Some(not_before) if not_before > now => Some(format!("The site asked us to wait; try again in {}s", not_before - now)),
+            _ => None,
+        }
+    }
+
+    fn record(&mut self, day: &Day, part: u8, answer: &str, outcome: Outcome) {
+        let attempts = self.parts.entry((day.key(), part)).or_default();
+        let answer = answer.to_string();
+
+        match outcome {
+            Outcome::Correct => attempts.correct = Some(answer),
+            Outcome::TooHigh => attempts.too_high.push(answer),
+            Outcome::TooLow => attempts.too_low.push(answer),
+            Outcome::Wrong => attempts.wrong.push(answer),
+            Outcome::AlreadySolved | Outcome::RateLimited(_) => (),
+        }
+    }
+}
+
+/// Send one part's answer, unless the log already knows what the site would say. Whatever the site says is recorded,
+/// along with how long it wants us to wait before the next one. `now` is in seconds since the Unix epoch.
+pub fn submit(config: &Config, log: &mut Log, day: &Day, part: u8, answer: &str, now: u64) -> Result<Outcome> {
+    let answer = answer.trim();
+    if answer.is_empty() || answer.contains('\n') {
+        return Err("Answers are a single line; read the letters off a picture and submit those".into());
+    }
+
+    if let Some(reason) = log.refuse(day, part, answer, now) {
+        return Err(reason.into());
+    }
+
+    let session = config
+        .session
+        .as_deref()
+        .ok_or_else(|| format!("No session token to submit with; set {} or `session` in the config file", fetch::SESSION_ENV))?;
+    let url = format!("{}/{}/day/{}/answer", config.base_url.trim_end_matches('/'), day.year, day.day);
+
+    let response = ureq::post(&url)
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", fetch::USER_AGENT)
+        .send_form(&[("level", &part.to_string()), ("answer", answer)])
+        .map_err(|e| fetch::failure(&url, day.year, day.day, e))?;
+    let html = response.into_string()?;
+
+    let outcome = Outcome::parse(&html)?;
+    log.record(day, part, answer, outcome);
+    if let Some(wait) = wait(&article(&html)) {
+        log.not_before = Some(now + wait.as_secs());
+    }
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::Server;
+
+    const RIGHT: &str = "<main>\n<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to saving Christmas. [<a href=\"/2015/day/1#part2\">Continue to Part Two</a>]</p></article>\n</main>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2015/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. [<a href=\"/2015/day/1\">Return to Day 1</a>]</p></article>";
+    const TOO_LOW: &str = "<article><p>That's not the right answer; your answer is too low.  Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again. [<a href=\"/2015/day/1\">Return to Day 1</a>]</p></article>";
+    const WRONG: &str = "<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. [<a href=\"/2015/day/1\">Return to Day 1</a>]</p></article>";
+    const TOO_SOON: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. [<a href=\"/2015/day/1\">Return to Day 1</a>]</p></article>";
+    const SOLVED: &str = "<article><p>You don't seem to be solving the right level.  Did you already complete it? [<a href=\"/2015/day/1\">Return to Day 1</a>]</p></article>";
+
+    fn day() -> Day {
+        Day {
+            year: 2015,
+            day: 1,
+            package: "test".to_string(),
+            workspace: Default::default(),
+            dir: Default::default(),
+        }
+    }
+
+    #[test]
+    fn outcomes() {
+        assert_eq!(Outcome::Correct, Outcome::parse(RIGHT).unwrap());
+        assert_eq!(Outcome::TooHigh, Outcome::parse(TOO_HIGH).unwrap());
+        assert_eq!(Outcome::TooLow, Outcome::parse(TOO_LOW).unwrap());
+        assert_eq!(Outcome::Wrong, Outcome::parse(WRONG).unwrap());
+        assert_eq!(Outcome::RateLimited(Duration::from_secs(65)), Outcome::parse(TOO_SOON).unwrap());
+        assert_eq!(Outcome::AlreadySolved, Outcome::parse(SOLVED).unwrap());
+        assert!(Outcome::parse("<html>Something else entirely</html>").is_err());
+    }
+
+    #[test]
+    fn waits() {
+        assert_eq!(Some(Duration::from_secs(60)), wait(&article(TOO_HIGH)));
+        assert_eq!(Some(Duration::from_secs(300)), wait(&article(TOO_LOW)));
+        assert_eq!(Some(Duration::from_secs(65)), wait(&article(TOO_SOON)));
+        assert_eq!(Some(Duration::from_secs(31)), wait("You have 31s left to wait."));
+        assert_eq!(None, wait(&article(RIGHT)));
+    }
+
+    #[test]
+    fn refusals() {
+        let mut log = Log::default();
+        log.record(&day(), 1, "500", Outcome::TooHigh);
+        log.record(&day(), 1, "100", Outcome::TooLow);
+        log.record(&day(), 1, "abc", Outcome::Wrong);
+
+        assert!(log.refuse(&day(), 1, "500", 0).unwrap().contains("already been tried"));
+        assert!(log.refuse(&day(), 1, "abc", 0).unwrap().contains("already been tried"));
+        assert!(log.refuse(&day(), 1, "600", 0).unwrap().contains("500 was already too high"));
+        assert!(log.refuse(&day(), 1, "42", 0).unwrap().contains("100 was already too low"));
+        assert_eq!(None, log.refuse(&day(), 1, "250", 0));
+        assert_eq!(None, log.refuse(&day(), 2, "500", 0));
+
+        log.not_before = Some(100);
+        assert!(log.refuse(&day(), 1, "250", 40).unwrap().contains("60s"));
+        assert_eq!(None, log.refuse(&day(), 1, "250", 100));
+
+        log.record(&day(), 1, "250", Outcome::Correct);
+        assert!(log.refuse(&day(), 1, "250", 100).unwrap().contains("already accepted"));
+        assert!(log.refuse(&day(), 1, "251", 100).unwrap().contains("already solved"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut log = Log { not_before: Some(1_700_000_060), ..Default::default() };
+        log.record(&day(), 1, "500", Outcome::TooHigh);
+        log.record(&day(), 1, "280", Outcome::Correct);
+        log.record(&day(), 2, "x\"y", Outcome::Wrong);
+
+        let toml = log.to_toml();
+        assert!(toml.contains("not_before = 1700000060\n"));
+        assert!(toml.contains("[\"2015-01\".part_1]\ncorrect = \"280\"\ntoo_high = [\"500\"]\n"));
+
+        assert_eq!(log, Log::parse(&toml).unwrap());
+        assert!(Log::parse("[\"2015-01\".part_3]\nwrong = [\"1\"]\n").is_err());
+    }
+
+    #[test]
+    fn submitting() {
+        // Only 280 is right; anything else is too high or too low, and then the site wants a minute's rest
+        let server = Server::start(|request| {
+            let answer = request.body.split('&').find_map(|p| p.strip_prefix("answer=")).unwrap_or("");
+            match answer.parse::<u32>() {
+                Ok(280) => ("200 OK", RIGHT.to_string()),
+                Ok(n) if n > 280 => ("200 OK", TOO_HIGH.to_string()),
+                _ => ("200 OK", TOO_LOW.replace("5 minutes", "one minute")),
+            }
+        });
+        let config = Config { session: Some("good".to_string()), base_url: server.url.clone() };
+        let mut log = Log::default();
+
+        assert_eq!(Outcome::TooHigh, submit(&config, &mut log, &day(), 1, "500\n", 1000).unwrap());
+        assert_eq!(Some(1060), log.not_before);
+
+        // Too soon, and then known wrong: neither reaches the site
+        assert!(submit(&config, &mut log, &day(), 1, "280", 1030).is_err());
+        assert!(submit(&config, &mut log, &day(), 1, "700", 1100).is_err());
+
+        assert_eq!(Outcome::Correct, submit(&config, &mut log, &day(), 1, "280", 1100).unwrap());
+        assert!(submit(&config, &mut log, &day(), 1, "280", 1200).is_err());
+
+        let requests = server.requests();
+        assert_eq!(2, requests.len());
+        assert_eq!(("POST", "/2015/day/1/answer"), (requests[0].method.as_str(), requests[0].path.as_str()));
+        assert_eq!("level=1&answer=500", requests[0].body);
+        assert!(requests[0].has_session("good"));
+    }
+}
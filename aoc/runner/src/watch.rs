@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::days::Day;
+use crate::exec::{self, Answers, Input};
+use crate::Result;
+
+/// How often to look for changes. Polling keeps us free of platform file-watching APIs, and a day's crate is only a
+/// handful of files.
+const POLL: Duration = Duration::from_millis(300);
+
+/// When every watched file was last modified. Two snapshots differing means something was saved, created or deleted.
+#[derive(Debug, Default, PartialEq)]
+pub struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    /// Everything in the day's crate (sources, fixtures, manifest, build script) other than build output, plus its
+    /// official input
+    pub fn take(day: &Day) -> Snapshot {
+        let mut files = BTreeMap::new();
+        collect(&day.dir, &mut files);
+
+        if let Ok(input) = day.default_input() {
+            if let Some(modified) = modified(&input) {
+                files.insert(input, modified);
+            }
+        }
+
+        Snapshot(files)
+    }
+}
+
+fn collect(dir: &Path, files: &mut BTreeMap<PathBuf, SystemTime>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.is_dir() {
+            if !path.ends_with("target") {
+                collect(&path, files);
+            }
+        } else if let Some(modified) = modified(&path) {
+            files.insert(path, modified);
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Rebuild and check a day every time one of its files changes, until interrupted: the tests (and so every fixture)
+/// first, then, only if they pass, the official input. Answers are compared with the previous run's.
+pub fn watch(day: &Day) -> Result<()> {
+    let mut previous: Option<Answers> = None;
+    let mut snapshot = Snapshot::take(day);
+
+    println!("👀 Watching {} (Ctrl-C to stop)", day.dir.strip_prefix(day.root()).unwrap_or(&day.dir).display());
+    cycle(day, &mut previous);
+
+    loop {
+        thread::sleep(POLL);
+
+        let latest = Snapshot::take(day);
+        if latest == snapshot {
+            continue;
+        }
+
+        // Let an editor finish writing (or a formatter finish rewriting) before building
+        thread::sleep(POLL);
+        snapshot = Snapshot::take(day);
+        cycle(day, &mut previous);
+    }
+}
+
+fn cycle(day: &Day, previous: &mut Option<Answers>) {
+    println!("\n🔁 {}", day);
+
+    if let Err(e) = exec::build(&[&day.workspace], Some(&day.package)) {
+        println!("💥 {}", e);
+        return;
+    }
+
+    match exec::test(day) {
+        Ok(true) => println!("✅ Tests pass"),
+        Ok(false) => {
+            println!("❌ Tests fail; not running the real input");
+            return;
+        }
+        Err(e) => {
+            println!("💥 Can't run the tests: {}", e);
+            return;
+        }
+    }
+
+    let run = match day.default_input().and_then(|input| exec::run(day, &Input::File(input), &exec::Options::default())) {
+        Ok(run) => run,
+        Err(e) => {
+            println!("💥 {}", e);
+            return;
+        }
+    };
+
+    for part in [1, 2] {
+        let before = previous.as_ref().and_then(|p| p.get(part));
+        println!("{}", describe(part, before, run.answers.get(part)));
+    }
+    println!("⏱  {:?}", run.elapsed);
+
+    *previous = Some(run.answers);
+}
+
+/// One part's answer, and how it compares with the last run's
+fn describe(part: u8, before: Option<&String>, now: Option<&String>) -> String {
+    let show = |answer: Option<&String>| match answer {
+        Some(answer) if answer.contains('\n') => format!("\n{}", answer),
+        Some(answer) => answer.clone(),
+        None => "(no answer)".to_string(),
+    };
+
+    match before {
+        Some(_) if before == now => format!("Part {}: {} (unchanged)", part, show(now)),
+        Some(_) => format!("Part {}: {} (was {})", part, show(now), show(before)),
+        None => format!("Part {}: {}", part, show(now)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn descriptions() {
+        let answer = |a: &str| Some(a.to_string());
+
+        assert_eq!("Part 1: 7", describe(1, None, answer("7").as_ref()));
+        assert_eq!("Part 1: 7 (unchanged)", describe(1, answer("7").as_ref(), answer("7").as_ref()));
+        assert_eq!("Part 2: 8 (was 7)", describe(2, answer("7").as_ref(), answer("8").as_ref()));
+        assert_eq!("Part 2: (no answer) (was 7)", describe(2, answer("7").as_ref(), None));
+        assert_eq!("Part 2: \n#.\n.# (was \n#.\n##)", describe(2, answer("#.\n##").as_ref(), answer("#.\n.#").as_ref()));
+    }
+
+    #[test]
+    fn snapshots_notice_changes() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::create_dir_all(dir.join("target/release")).unwrap();
+        fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+
+        let day = Day {
+            year: 2015,
+            day: 1,
+            package: "test".to_string(),
+            workspace: dir.clone(),
+            dir: dir.clone(),
+        };
+
+        let before = Snapshot::take(&day);
+        assert_eq!(1, before.0.len());
+
+        // Build output doesn't count
+        fs::write(dir.join("target/release/test"), "binary").unwrap();
+        assert_eq!(before, Snapshot::take(&day));
+
+        fs::create_dir_all(dir.join("fixtures")).unwrap();
+        fs::write(dir.join("fixtures/edge.txt"), "part_1 = 1\n---\n1").unwrap();
+        assert_ne!(before, Snapshot::take(&day));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}